@@ -0,0 +1,320 @@
+//! Embeds the [`ethereum-lists/chains`][ethereum-list-chains] chain files and
+//! generates lookup tables from them, for the APIs which don't need the full
+//! `Chain` struct.
+//!
+//! [ethereum-list-chains]: https://github.com/ethereum-lists/chains
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use filter::{tolerant_u64, Filter};
+
+#[path = "build/filter.rs"]
+mod filter;
+#[path = "src/sha256.rs"]
+mod sha256;
+#[path = "src/testnet.rs"]
+mod testnet;
+
+const CHAINS_DIR: &str = "ethereum-list/chains/_data/chains";
+const ICONS_DIR: &str = "ethereum-list/chains/_data/icons";
+/// Only the chains matching this filter expression are embedded, see `build/filter.rs`
+const FILTER_ENV: &str = "EVM_CHAINS_FILTER";
+
+/// The fields of a chain file needed for the generated tables.
+///
+/// Only the chain id of the file name is required to embed a chain file, the
+/// rest of the validation is left to `ChainRegistry::try_load`.
+struct TableRow {
+    chain_id: u64,
+    name: String,
+    symbol: String,
+    decimals: u8,
+    /// The rest of the `chains_mini.json` fields, for the `mini` feature
+    short_name: String,
+    rpc: Vec<String>,
+    currency_name: String,
+}
+
+/// A chain file to embed with `include_str!`.
+struct ChainFile {
+    /// The chain id from the file name `eip155-CHAIN_ID.json`
+    chain_id: u64,
+    path: PathBuf,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", CHAINS_DIR);
+    println!("cargo:rerun-if-changed={}", ICONS_DIR);
+    println!("cargo:rerun-if-changed=build/filter.rs");
+    println!("cargo:rerun-if-changed=src/testnet.rs");
+    println!("cargo:rerun-if-changed=src/sha256.rs");
+    println!("cargo:rerun-if-env-changed={}", FILTER_ENV);
+
+    let filter = env::var(FILTER_ENV).ok().map(|expression| {
+        Filter::parse(&expression).unwrap_or_else(|err| {
+            panic!(
+                "Invalid {} expression {:?}: {}",
+                FILTER_ENV, expression, err
+            )
+        })
+    });
+
+    let (mut rows, mut files) = read_chain_files(filter.as_ref());
+    rows.sort_unstable_by_key(|row| row.chain_id);
+    files.sort_unstable_by_key(|file| file.chain_id);
+
+    if let Some(pair) = files
+        .windows(2)
+        .find(|pair| pair[0].chain_id == pair[1].chain_id)
+    {
+        panic!("Duplicate Chain id ({})", pair[0].chain_id)
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR should be set by cargo");
+    fs::write(Path::new(&out_dir).join("tables.rs"), tables(&rows))
+        .expect("Should write the generated tables");
+    fs::write(Path::new(&out_dir).join("mini.rs"), mini_chains(&rows))
+        .expect("Should write the mini chains");
+    fs::write(Path::new(&out_dir).join("chains.rs"), chain_files(&files))
+        .expect("Should write the embedded chain files");
+    fs::write(Path::new(&out_dir).join("icons.rs"), icon_files())
+        .expect("Should write the embedded icon files");
+}
+
+/// `(icon name, icon file json)` of all the `_data/icons/NAME.json` files, sorted by name.
+fn icon_files() -> String {
+    let mut icons = fs::read_dir(ICONS_DIR)
+        .map(|icon_files| {
+            icon_files
+                .map(|entry_result| {
+                    entry_result
+                        .expect("Failed to read directory entry from icons data directory")
+                        .path()
+                })
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .is_some_and(|extension| extension == "json")
+                })
+                .collect::<Vec<_>>()
+        })
+        // no icons without the submodule, the missing chains are already reported
+        .unwrap_or_default();
+    icons.sort_unstable();
+
+    let mut icon_files = String::new();
+    writeln!(
+        icon_files,
+        "pub(crate) const ICON_FILES: [(&str, &str); {}] = [",
+        icons.len()
+    )
+    .unwrap();
+    for path in icons {
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .expect("Icon file name should be valid Unicode");
+        let path = fs::canonicalize(&path).expect("Icon file path should be canonicalizable");
+        let path = path
+            .to_str()
+            .expect("Icon file path should be valid Unicode");
+        writeln!(icon_files, "    ({:?}, include_str!({:?})),", name, path).unwrap();
+    }
+    writeln!(icon_files, "];").unwrap();
+
+    icon_files
+}
+
+fn read_chain_files(filter: Option<&Filter>) -> (Vec<TableRow>, Vec<ChainFile>) {
+    let chain_files = match fs::read_dir(CHAINS_DIR) {
+        Ok(chain_files) => chain_files,
+        Err(err) => {
+            // don't fail the build, e.g. for `cargo doc` without the submodule
+            println!(
+                "cargo:warning=Chains directory {} is not readable, is the `ethereum-list/chains` submodule checked out? {}",
+                CHAINS_DIR, err
+            );
+            return (Vec::new(), Vec::new());
+        }
+    };
+
+    let mut rows = Vec::new();
+    let mut files = Vec::new();
+    for entry_result in chain_files {
+        let dir_entry =
+            entry_result.expect("Failed to read directory entry from chains data directory");
+        let path = dir_entry.path();
+
+        if !path.is_file() || path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+
+        // Strip the prefix `eip155-` & suffix `.json` of the file name
+        let chain_id = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .and_then(|file_name| file_name.strip_prefix("eip155-"))
+            .and_then(|file_name| file_name.strip_suffix(".json"))
+            .and_then(|chain_id| chain_id.parse::<u64>().ok())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Chain file {} was in incorrect form, expected: eip155-CHAIN_ID.json",
+                    path.display()
+                )
+            });
+
+        let json = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Failed to read chain file {}: {}", path.display(), err));
+        // malformed chain files are still embedded, `ChainRegistry::try_load` reports them
+        let value = serde_json::from_str::<Value>(&json).ok();
+        if let (Some(filter), Some(value)) = (filter, &value) {
+            if !filter.matches(value) {
+                continue;
+            }
+        }
+
+        files.push(ChainFile {
+            chain_id,
+            path: fs::canonicalize(&path).expect("Chain file path should be canonicalizable"),
+        });
+
+        match value.as_ref().and_then(|value| table_row(chain_id, value)) {
+            Some(row) => rows.push(row),
+            None => println!(
+                "cargo:warning=Chain file {} is malformed and left out of the generated tables",
+                path.display()
+            ),
+        }
+    }
+
+    (rows, files)
+}
+
+/// The table fields of the chain file, `None` if any is missing or invalid.
+fn table_row(chain_id: u64, value: &Value) -> Option<TableRow> {
+    let str_field = |pointer: &str| value.pointer(pointer)?.as_str().map(String::from);
+
+    Some(TableRow {
+        chain_id,
+        name: str_field("/name")?,
+        symbol: str_field("/nativeCurrency/symbol")?,
+        decimals: tolerant_u64(value.pointer("/nativeCurrency/decimals")?)
+            .and_then(|decimals| u8::try_from(decimals).ok())?,
+        short_name: str_field("/shortName")?,
+        rpc: value
+            .pointer("/rpc")?
+            .as_array()?
+            .iter()
+            .map(|rpc| rpc.as_str().map(String::from))
+            .collect::<Option<_>>()?,
+        currency_name: str_field("/nativeCurrency/name")?,
+    })
+}
+
+fn tables(rows: &[TableRow]) -> String {
+    let mut tables = String::new();
+
+    let mut table = |name: &str, value_type: &str, values: &mut dyn Iterator<Item = String>| {
+        writeln!(
+            tables,
+            "pub(crate) const {}: [(u64, {}); {}] = [",
+            name,
+            value_type,
+            rows.len()
+        )
+        .unwrap();
+        for (row, value) in rows.iter().zip(values) {
+            writeln!(tables, "    ({}, {}),", row.chain_id, value).unwrap();
+        }
+        writeln!(tables, "];").unwrap();
+    };
+
+    table(
+        "NAMES",
+        "&str",
+        &mut rows.iter().map(|row| format!("{:?}", row.name)),
+    );
+    table(
+        "SYMBOLS",
+        "&str",
+        &mut rows.iter().map(|row| format!("{:?}", row.symbol)),
+    );
+    table(
+        "DECIMALS",
+        "u8",
+        &mut rows.iter().map(|row| row.decimals.to_string()),
+    );
+
+    tables
+}
+
+/// The `ChainMini` of every chain, in chain id order like the tables.
+fn mini_chains(rows: &[TableRow]) -> String {
+    let mut mini_chains = String::new();
+
+    writeln!(
+        mini_chains,
+        "pub(crate) static CHAINS: [super::ChainMini; {}] = [",
+        rows.len()
+    )
+    .unwrap();
+    for row in rows {
+        writeln!(
+            mini_chains,
+            "    super::ChainMini {{ name: {:?}, chain_id: {}, short_name: {:?}, rpc: &{:?}, native_currency: super::NativeCurrencyMini {{ name: {:?}, symbol: {:?}, decimals: {} }} }},",
+            row.name, row.chain_id, row.short_name, row.rpc, row.currency_name, row.symbol, row.decimals
+        )
+        .unwrap();
+    }
+    writeln!(mini_chains, "];").unwrap();
+
+    mini_chains
+}
+
+fn chain_files(files: &[ChainFile]) -> String {
+    let mut chain_files = String::new();
+
+    writeln!(
+        chain_files,
+        "pub(crate) const CHAIN_FILES: [(u64, &str); {}] = [",
+        files.len()
+    )
+    .unwrap();
+    for file in files {
+        let path = file
+            .path
+            .to_str()
+            .expect("Chain file path should be valid Unicode");
+        writeln!(
+            chain_files,
+            "    ({}, include_str!({:?})),",
+            file.chain_id, path
+        )
+        .unwrap();
+    }
+    writeln!(chain_files, "];").unwrap();
+
+    // the files as read now, `include_str!` reads them again when compiling
+    let mut embedded = Vec::new();
+    for file in files {
+        let json = fs::read(&file.path).expect("Should read the chain file");
+        embedded.extend(file.chain_id.to_be_bytes());
+        embedded.extend((json.len() as u64).to_be_bytes());
+        embedded.extend(json);
+    }
+    writeln!(
+        chain_files,
+        "/// SHA-256 of the chain id, json length (both big-endian) & json of every chain file, in chain id order\npub(crate) const DIGEST: [u8; 32] = {:?};",
+        sha256::sha256(&embedded)
+    )
+    .unwrap();
+
+    chain_files
+}
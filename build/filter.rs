@@ -0,0 +1,274 @@
+//! The `EVM_CHAINS_FILTER` expressions, evaluated against the raw chain files.
+//!
+//! ```text
+//! expr  := or
+//! or    := and ("||" and)*
+//! and   := unary ("&&" unary)*
+//! unary := "!" unary | "(" expr ")" | atom
+//! atom  := "mainnet" | "testnet" | "flagged" | "deprecated" | "eip1559"
+//!        | "id=" integer | "symbol=" string
+//! ```
+//!
+//! E.g. `EVM_CHAINS_FILTER="mainnet && !flagged || id=11155111"`
+use serde_json::Value;
+
+use crate::testnet::is_testnet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// Not a [`Filter::Testnet`]
+    Mainnet,
+    /// A testnet by the definition of `Chain::is_testnet`, see `src/testnet.rs`
+    Testnet,
+    /// `redFlags` is not empty
+    Flagged,
+    /// `status` is `deprecated`
+    Deprecated,
+    /// `features` contains `EIP1559`, ignoring case
+    Eip1559,
+    /// `chainId`, also when encoded as a string like the loader accepts
+    Id(u64),
+    /// Case-insensitive `nativeCurrency.symbol`
+    Symbol(String),
+    Not(Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    Word(String),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '!' => tokens.push(Token::Not),
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '&' | '|' => match chars.next() {
+                Some(next) if next == c => {
+                    tokens.push(if c == '&' { Token::And } else { Token::Or })
+                }
+                _ => return Err(format!("expected `{0}{0}`", c)),
+            },
+            c if c.is_alphanumeric() || "_-.=".contains(c) => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || "_-.=".contains(next)) {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+            c => return Err(format!("unexpected character `{}`", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Filter, String> {
+        let mut filter = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        Ok(filter)
+    }
+
+    fn and(&mut self) -> Result<Filter, String> {
+        let mut filter = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            filter = Filter::And(Box::new(filter), Box::new(self.unary()?));
+        }
+        Ok(filter)
+    }
+
+    fn unary(&mut self) -> Result<Filter, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Filter::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let filter = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(filter),
+                    _ => Err("expected `)`".into()),
+                }
+            }
+            Some(Token::Word(word)) => atom(&word),
+            Some(token) => Err(format!("unexpected {:?}", token)),
+            None => Err("unexpected end of expression".into()),
+        }
+    }
+}
+
+fn atom(word: &str) -> Result<Filter, String> {
+    match word.split_once('=') {
+        Some(("id", id)) => id
+            .parse()
+            .map(Filter::Id)
+            .map_err(|_| format!("invalid chain id `{}`", id)),
+        Some(("symbol", symbol)) if !symbol.is_empty() => Ok(Filter::Symbol(symbol.into())),
+        Some(_) => Err(format!("unknown condition `{}`", word)),
+        None => match word {
+            "mainnet" => Ok(Filter::Mainnet),
+            "testnet" => Ok(Filter::Testnet),
+            "flagged" => Ok(Filter::Flagged),
+            "deprecated" => Ok(Filter::Deprecated),
+            "eip1559" => Ok(Filter::Eip1559),
+            _ => Err(format!("unknown condition `{}`", word)),
+        },
+    }
+}
+
+impl Filter {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            position: 0,
+        };
+
+        let filter = parser.or()?;
+        match parser.next() {
+            None => Ok(filter),
+            Some(token) => Err(format!("unexpected {:?}", token)),
+        }
+    }
+
+    /// Evaluates the filter against the raw JSON of a chain file.
+    pub fn matches(&self, chain: &Value) -> bool {
+        let str_field = |pointer: &str| chain.pointer(pointer).and_then(Value::as_str);
+
+        match self {
+            Filter::Mainnet => !Filter::Testnet.matches(chain),
+            Filter::Testnet => is_testnet(
+                str_field("/name").unwrap_or_default(),
+                str_field("/network").unwrap_or_default(),
+                chain
+                    .get("faucets")
+                    .and_then(Value::as_array)
+                    .is_some_and(|faucets| !faucets.is_empty()),
+            ),
+            Filter::Flagged => chain
+                .get("redFlags")
+                .and_then(Value::as_array)
+                .is_some_and(|flags| !flags.is_empty()),
+            Filter::Deprecated => str_field("/status") == Some("deprecated"),
+            Filter::Eip1559 => {
+                chain
+                    .get("features")
+                    .and_then(Value::as_array)
+                    .is_some_and(|features| {
+                        features.iter().any(|feature| {
+                            feature
+                                .get("name")
+                                .and_then(Value::as_str)
+                                .is_some_and(|name| name.eq_ignore_ascii_case("EIP1559"))
+                        })
+                    })
+            }
+            Filter::Id(id) => chain.get("chainId").and_then(tolerant_u64) == Some(*id),
+            Filter::Symbol(symbol) => str_field("/nativeCurrency/symbol")
+                .is_some_and(|actual| actual.eq_ignore_ascii_case(symbol)),
+            Filter::Not(filter) => !filter.matches(chain),
+            Filter::And(left, right) => left.matches(chain) && right.matches(chain),
+            Filter::Or(left, right) => left.matches(chain) || right.matches(chain),
+        }
+    }
+}
+
+/// Accepts integers encoded as numbers, integral floats or strings, see `src/de.rs`.
+pub fn tolerant_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64().or_else(|| {
+            number
+                .as_f64()
+                .filter(|float| float.fract() == 0.0 && *float >= 0.0)
+                .map(|float| float as u64)
+        }),
+        Value::String(string) => string.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_precedence() {
+        assert_eq!(
+            Ok(Filter::Or(
+                Box::new(Filter::And(
+                    Box::new(Filter::Mainnet),
+                    Box::new(Filter::Not(Box::new(Filter::Flagged)))
+                )),
+                Box::new(Filter::Id(5))
+            )),
+            Filter::parse("mainnet && !flagged || id=5")
+        );
+        assert!(Filter::parse("mainnet &&").is_err());
+        assert!(Filter::parse("(mainnet").is_err());
+        assert!(Filter::parse("mainnet & testnet").is_err());
+        assert!(Filter::parse("l2").is_err());
+    }
+
+    #[test]
+    fn matches_chain_files() {
+        let ethereum = json!({
+            "name": "Ethereum Mainnet",
+            "chainId": 1,
+            "network": "mainnet",
+            "nativeCurrency": { "symbol": "ETH" },
+            "features": [{ "name": "EIP155" }, { "name": "EIP1559" }]
+        });
+        let flagged = json!({
+            "name": "Dogechain Mainnet",
+            "chainId": 2000,
+            "network": "mainnet",
+            "nativeCurrency": { "symbol": "DOGE" },
+            "redFlags": ["reusedChainId"]
+        });
+
+        let filter = Filter::parse("mainnet && !flagged && (eip1559 || symbol=doge)").unwrap();
+        assert!(filter.matches(&ethereum));
+        assert!(!filter.matches(&flagged));
+        assert!(Filter::parse("testnet || id=2000")
+            .unwrap()
+            .matches(&flagged));
+
+        // like `Chain::is_testnet`, the name makes it a testnet despite the `network`
+        let mislabeled = json!({ "name": "Example Testnet", "network": "mainnet", "chainId": 9 });
+        assert!(Filter::parse("testnet").unwrap().matches(&mislabeled));
+    }
+}
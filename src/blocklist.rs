@@ -0,0 +1,164 @@
+//! Blocklist of known-malicious or deprecated RPC hosts.
+//!
+//! The blocklist is applied by [`Chain::rpc_urls`], the raw list of endpoints
+//! is still available through [`Chain::rpc`].
+use serde::{Deserialize, Serialize};
+
+use crate::{error, registry, url, Chain, ChainRegistry, Error, RegistryBuilder};
+
+/// Hosts which have been shut down but are still listed upstream.
+const BUNDLED: &[(&str, BlockReason)] = &[
+    ("api.mycryptoapi.com", BlockReason::Deprecated),
+    ("bsc-ws-node.nariox.org", BlockReason::Deprecated),
+    ("rpc.goerli.mudit.blog", BlockReason::Deprecated),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockReason {
+    Malicious,
+    Deprecated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedHost {
+    /// An exact host (`rpc.example.com`) or a domain with all its subdomains (`*.example.com`)
+    pub host: String,
+    pub reason: BlockReason,
+}
+
+impl BlockedHost {
+    pub fn matches(&self, host: &str) -> bool {
+        match self.host.strip_prefix("*.") {
+            Some(domain) => url::is_same_or_subdomain(host, domain),
+            None => host.eq_ignore_ascii_case(&self.host),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RpcBlocklist {
+    pub hosts: Vec<BlockedHost>,
+}
+
+impl RpcBlocklist {
+    /// The blocklist shipped with the crate.
+    pub fn bundled() -> Self {
+        Self {
+            hosts: BUNDLED
+                .iter()
+                .map(|&(host, reason)| BlockedHost {
+                    host: host.into(),
+                    reason,
+                })
+                .collect(),
+        }
+    }
+
+    /// Parses a user-supplied blocklist, a JSON array of `{ "host", "reason" }` objects.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(error::deserialize)
+    }
+
+    pub fn extend(mut self, other: RpcBlocklist) -> Self {
+        self.hosts.extend(other.hosts);
+        self
+    }
+
+    /// The reason the url is blocked, if it is.
+    pub fn blocked(&self, rpc_url: &str) -> Option<BlockReason> {
+        let host = url::host(rpc_url)?;
+
+        self.hosts
+            .iter()
+            .find_map(|blocked| blocked.matches(host).then_some(blocked.reason))
+    }
+
+    pub fn is_blocked(&self, rpc_url: &str) -> bool {
+        self.blocked(rpc_url).is_some()
+    }
+}
+
+impl RegistryBuilder {
+    /// Replaces the [`RpcBlocklist::bundled`] blocklist applied by the
+    /// [`Chain::rpc_urls`] of the registry's chains.
+    ///
+    /// Extend the bundled one to keep its hosts blocked, e.g.
+    /// `RpcBlocklist::bundled().extend(RpcBlocklist::from_json(json)?)`.
+    pub fn rpc_blocklist(mut self, blocklist: RpcBlocklist) -> Self {
+        self.overlays.rpc_blocklist = blocklist;
+        self
+    }
+}
+
+impl ChainRegistry {
+    /// The blocklist applied by [`Chain::rpc_urls`], see [`RegistryBuilder::rpc_blocklist`].
+    pub fn rpc_blocklist(&self) -> &RpcBlocklist {
+        &self.overlays().rpc_blocklist
+    }
+}
+
+/// See [`ChainRegistry::rpc_blocklist`].
+pub fn rpc_blocklist() -> &'static RpcBlocklist {
+    &registry::overlays().rpc_blocklist
+}
+
+impl Chain {
+    /// The [`Chain::rpc`] endpoints, without the ones on the blocklist or not
+    /// allowed by the url policy of the registry the chain was loaded by.
+    pub fn rpc_urls(&self) -> impl Iterator<Item = &str> {
+        self.rpc_urls_excluding(&self.overlays.rpc_blocklist)
+    }
+
+    /// The [`Chain::rpc`] endpoints, without the ones on the given blocklist
+    /// or not allowed by the url policy of the registry the chain was loaded by.
+    pub fn rpc_urls_excluding<'a>(
+        &'a self,
+        blocklist: &'a RpcBlocklist,
+    ) -> impl Iterator<Item = &'a str> {
+        self.rpc.iter().map(String::as_str).filter(move |rpc_url| {
+            !blocklist.is_blocked(rpc_url) && self.overlays.url_policy.allows(rpc_url)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_blocklist() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert!(ethereum.rpc.iter().any(|rpc| rpc.contains("mycryptoapi")));
+        assert!(ethereum.rpc_urls().all(|rpc| !rpc.contains("mycryptoapi")));
+    }
+
+    #[test]
+    fn user_supplied_blocklist() {
+        let blocklist =
+            RpcBlocklist::from_json(r#"[{ "host": "*.infura.io", "reason": "malicious" }]"#)
+                .expect("Should deserialize blocklist");
+
+        assert_eq!(
+            Some(BlockReason::Malicious),
+            blocklist.blocked("https://mainnet.infura.io/v3/${INFURA_API_KEY}")
+        );
+        assert_eq!(None, blocklist.blocked("https://cloudflare-eth.com"));
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert!(ethereum
+            .rpc_urls_excluding(&blocklist)
+            .all(|rpc| !rpc.contains("infura.io")));
+
+        let registry = ChainRegistry::builder()
+            .rpc_blocklist(blocklist)
+            .load()
+            .expect("Should load the embedded chains");
+        let ethereum = registry.get(1).expect("Chain(1) should exist");
+        assert!(ethereum.rpc_urls().all(|rpc| !rpc.contains("infura.io")));
+        assert!(ethereum.rpc_urls().any(|rpc| rpc.contains("mycryptoapi")));
+    }
+}
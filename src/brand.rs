@@ -0,0 +1,132 @@
+//! Visual identity of chains: brand colors and identicons.
+use std::fmt;
+
+use crate::Chain;
+
+/// Brand colors of well known chains, everything else gets a derived color.
+const BRAND_COLORS: &[(u64, Rgb)] = &[
+    (1, Rgb(0x62, 0x7E, 0xEA)),
+    (10, Rgb(0xFF, 0x04, 0x20)),
+    (56, Rgb(0xF0, 0xB9, 0x0B)),
+    (100, Rgb(0x04, 0x79, 0x5B)),
+    (137, Rgb(0x82, 0x47, 0xE5)),
+    (250, Rgb(0x19, 0x69, 0xFF)),
+    (324, Rgb(0x8C, 0x8D, 0xFC)),
+    (8453, Rgb(0x00, 0x52, 0xFF)),
+    (42161, Rgb(0x28, 0xA0, 0xF0)),
+    (43114, Rgb(0xE8, 0x41, 0x42)),
+];
+
+/// The background of [`Chain::identicon_svg`]
+const IDENTICON_BACKGROUND: Rgb = Rgb(0xF0, 0xF0, 0xF0);
+
+/// A color, displayed as `#rrggbb`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl Chain {
+    /// The curated brand color of the chain or a color derived from the chain id.
+    ///
+    /// Derived colors are stable across releases.
+    pub fn brand_color(&self) -> Rgb {
+        BRAND_COLORS
+            .iter()
+            .find_map(|&(chain_id, color)| (chain_id == self.chain_id).then_some(color))
+            .unwrap_or_else(|| derived_color(self.chain_id))
+    }
+
+    /// A 5x5 horizontally symmetric identicon derived from the chain id and
+    /// drawn in the [`Chain::brand_color`].
+    pub fn identicon_svg(&self) -> String {
+        let hash = fnv1a(&self.chain_id.to_be_bytes());
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 5 5" shape-rendering="crispEdges"><rect width="5" height="5" fill="{}"/>"#,
+            IDENTICON_BACKGROUND
+        );
+        svg.push_str(&format!(r#"<g fill="{}">"#, self.brand_color()));
+        // 3 columns x 5 rows are derived, the last 2 columns mirror the first 2
+        for row in 0..5 {
+            for column in 0..3 {
+                if hash >> (row * 3 + column) & 1 == 0 {
+                    continue;
+                }
+
+                svg.push_str(&format!(
+                    r#"<rect x="{}" y="{}" width="1" height="1"/>"#,
+                    column, row
+                ));
+                if column < 2 {
+                    svg.push_str(&format!(
+                        r#"<rect x="{}" y="{}" width="1" height="1"/>"#,
+                        4 - column,
+                        row
+                    ));
+                }
+            }
+        }
+        svg.push_str("</g></svg>");
+
+        svg
+    }
+}
+
+/// A saturated, mid-lightness color with a hue derived from the chain id.
+fn derived_color(chain_id: u64) -> Rgb {
+    let hue = (fnv1a(&chain_id.to_be_bytes()) % 360) as f64;
+    let (saturation, lightness) = (0.65, 0.5);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0_f64).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+    let (r, g, b) = match hue as u64 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+
+    Rgb(channel(r), channel(g), channel(b))
+}
+
+/// 64-bit FNV-1a, a tiny and stable hash
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brand_colors() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert_eq!("#627eea", ethereum.brand_color().to_string());
+
+        let mut unknown = ethereum.clone();
+        unknown.chain_id = 123_456_789;
+        assert_eq!(unknown.brand_color(), derived_color(123_456_789));
+        assert_ne!(derived_color(123_456_789), derived_color(123_456_790));
+    }
+
+    #[test]
+    fn identicon_is_deterministic() {
+        let polygon = Chain::get(137).expect("Chain(137) should exist");
+        let svg = polygon.identicon_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r##"<g fill="#8247e5">"##));
+        assert_eq!(svg, polygon.identicon_svg());
+    }
+}
@@ -0,0 +1,112 @@
+//! Looking up many chains at once, e.g. to validate a configuration file.
+use crate::{error, Chain, ChainRegistry, Error};
+
+/// The result of [`ChainRegistry::get_many`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkResult<'a> {
+    /// The chains found, in the order they were requested
+    pub found: Vec<&'a Chain>,
+    /// The unknown chain ids, in the order they were requested
+    pub missing: Vec<u64>,
+}
+
+impl BulkResult<'_> {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+impl ChainRegistry {
+    /// Looks up all the chain ids, reporting every unknown one instead of
+    /// stopping on the first.
+    pub fn get_many(&self, chain_ids: impl IntoIterator<Item = u64>) -> BulkResult<'_> {
+        let mut result = BulkResult {
+            found: Vec::new(),
+            missing: Vec::new(),
+        };
+
+        for chain_id in chain_ids {
+            match self.get(chain_id) {
+                Some(chain) => result.found.push(chain),
+                None => result.missing.push(chain_id),
+            }
+        }
+
+        result
+    }
+
+    /// Looks up all the chain ids and fails with all the unknown ones, if any.
+    pub fn try_get_many(
+        &self,
+        chain_ids: impl IntoIterator<Item = u64>,
+    ) -> Result<Vec<&Chain>, Error> {
+        let result = self.get_many(chain_ids);
+
+        match result.is_complete() {
+            true => Ok(result.found),
+            false => Err(error::not_found(result.missing)),
+        }
+    }
+}
+
+impl Chain {
+    /// See [`ChainRegistry::get_many`], all the chain ids are missing if the
+    /// [`ChainRegistry::global`] registry failed to load.
+    pub fn get_many(chain_ids: impl IntoIterator<Item = u64>) -> BulkResult<'static> {
+        match ChainRegistry::global() {
+            Ok(registry) => registry.get_many(chain_ids),
+            Err(_) => BulkResult {
+                found: Vec::new(),
+                missing: chain_ids.into_iter().collect(),
+            },
+        }
+    }
+
+    /// See [`ChainRegistry::try_get_many`], fails with the error of the
+    /// [`ChainRegistry::global`] registry if it failed to load.
+    pub fn try_get_many(
+        chain_ids: impl IntoIterator<Item = u64>,
+    ) -> Result<Vec<&'static Chain>, Error> {
+        ChainRegistry::global()
+            .map_err(Error::clone)?
+            .try_get_many(chain_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Kind;
+
+    #[test]
+    fn partial_results() {
+        let result = Chain::get_many([137, u64::MAX, 1, u64::MAX - 1]);
+
+        assert_eq!(
+            vec![137, 1],
+            result
+                .found
+                .iter()
+                .map(|chain| chain.chain_id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![u64::MAX, u64::MAX - 1], result.missing);
+        assert!(!result.is_complete());
+
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        assert!(std::ptr::eq(
+            registry.get(137).unwrap(),
+            registry.get_many([137]).found[0]
+        ));
+    }
+
+    #[test]
+    fn strict_lookup() {
+        assert_eq!(2, Chain::try_get_many([1, 56]).unwrap().len());
+
+        let error = Chain::try_get_many([1, u64::MAX]).expect_err("Chain should be missing");
+        assert_eq!(Kind::NotFound, error.kind());
+        assert!(error.is_not_found());
+        assert_eq!(Some(u64::MAX), error.chain_id());
+    }
+}
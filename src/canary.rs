@@ -0,0 +1,110 @@
+//! Canary mode: which upstream fields the [`Chain`] data model doesn't
+//! cover yet.
+//!
+//! Every chain file is deserialized leniently (unknown fields are ignored)
+//! and compared with the raw JSON, like a `deny_unknown_fields` parse would,
+//! except all unknown fields are reported instead of only the first one.
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::{read_chain_file, Chain, Error};
+
+/// An upstream field which the data model doesn't deserialize.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelGap {
+    /// Path of the field, with `[]` for array elements, e.g. `explorers[].icon`
+    pub field: String,
+    /// The chains which have the field, in ascending order
+    pub chain_ids: Vec<u64>,
+}
+
+/// Loads all the chain files again and reports the fields not modeled by
+/// [`Chain`], sorted by field path.
+pub fn model_gaps() -> Result<Vec<ModelGap>, Error> {
+    let mut gaps = BTreeMap::<String, Vec<u64>>::new();
+
+    for chain in Chain::iter() {
+        let raw = read_chain_file(chain.chain_id)?;
+        let modeled = serde_json::to_value(chain).expect("Chain should serialize to JSON value");
+
+        let mut fields = Vec::new();
+        unknown_fields(&raw, &modeled, "", &mut fields);
+        for field in fields {
+            let chain_ids = gaps.entry(field).or_default();
+            if chain_ids.last() != Some(&chain.chain_id) {
+                chain_ids.push(chain.chain_id);
+            }
+        }
+    }
+
+    Ok(gaps
+        .into_iter()
+        .map(|(field, chain_ids)| ModelGap { field, chain_ids })
+        .collect())
+}
+
+/// Collects the paths of the fields in `raw` which are missing in `modeled`.
+fn unknown_fields(raw: &Value, modeled: &Value, path: &str, fields: &mut Vec<String>) {
+    match (raw, modeled) {
+        (Value::Object(raw), Value::Object(modeled)) => {
+            for (key, raw_value) in raw {
+                let field = match path {
+                    "" => key.clone(),
+                    path => format!("{}.{}", path, key),
+                };
+
+                match modeled.get(key) {
+                    Some(modeled_value) => unknown_fields(raw_value, modeled_value, &field, fields),
+                    None => fields.push(field),
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(modeled)) => {
+            let field = format!("{}[]", path);
+            for (raw_value, modeled_value) in raw.iter().zip(modeled) {
+                unknown_fields(raw_value, modeled_value, &field, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn nested_unknown_fields() {
+        let raw = json!({
+            "name": "Chain",
+            "status": "active",
+            "nativeCurrency": { "symbol": "ETH", "logo": "eth" },
+            "explorers": [{ "url": "https://a" }, { "url": "https://b", "icon": "b" }]
+        });
+        let modeled = json!({
+            "name": "Chain",
+            "nativeCurrency": { "symbol": "ETH" },
+            "explorers": [{ "url": "https://a" }, { "url": "https://b" }]
+        });
+
+        let mut fields = Vec::new();
+        unknown_fields(&raw, &modeled, "", &mut fields);
+        fields.sort();
+
+        assert_eq!(
+            vec!["explorers[].icon", "nativeCurrency.logo", "status"],
+            fields
+        );
+    }
+
+    #[test]
+    fn loaded_model_gaps() {
+        let gaps = model_gaps().expect("Should read all chain files");
+
+        assert!(gaps.windows(2).all(|pair| pair[0].field < pair[1].field));
+        assert!(gaps.iter().all(|gap| !gap.chain_ids.is_empty()));
+    }
+}
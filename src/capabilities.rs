@@ -0,0 +1,137 @@
+//! A chain × capability matrix, for reasoning about feature coverage
+//! across networks.
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::{Chain, ChainRegistry, Feature};
+
+/// The capabilities of a chain, as declared by its chain file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub eip1559: bool,
+    pub ens: bool,
+    pub faucet: bool,
+    /// Has an [EIP-3091](https://eips.ethereum.org/EIPS/eip-3091) explorer
+    pub eip3091_explorer: bool,
+    pub websocket_rpc: bool,
+    /// The [`Chain::parent`] is of type `L2`
+    pub l2: bool,
+}
+
+/// The column names of [`CapabilityMatrix::to_csv`], in order.
+const CSV_HEADER: &str = "chainId,name,eip1559,ens,faucet,eip3091Explorer,websocketRpc,l2";
+
+/// A row of the [`CapabilityMatrix`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityRow {
+    pub chain_id: u64,
+    pub name: String,
+    #[serde(flatten)]
+    pub capabilities: Capabilities,
+}
+
+/// The [`Capabilities`] of the chains of a registry, in ascending chain id order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct CapabilityMatrix {
+    pub rows: Vec<CapabilityRow>,
+}
+
+impl CapabilityMatrix {
+    /// A JSON array of rows, e.g. `[{"chainId":1,"name":"Ethereum Mainnet","eip1559":true,...}]`
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Capability matrix should serialize to JSON")
+    }
+
+    /// A CSV table with a header row, the capabilities are `true` or `false`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", CSV_HEADER);
+        for row in &self.rows {
+            let capabilities = row.capabilities;
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{}",
+                row.chain_id,
+                csv_field(&row.name),
+                capabilities.eip1559,
+                capabilities.ens,
+                capabilities.faucet,
+                capabilities.eip3091_explorer,
+                capabilities.websocket_rpc,
+                capabilities.l2
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+}
+
+/// Quotes fields with separators, quotes or line breaks, as in RFC 4180.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+impl Chain {
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            eip1559: self.has_feature(&Feature::Eip1559),
+            ens: self.ens.is_some(),
+            faucet: !self.faucets.is_empty(),
+            eip3091_explorer: self.explorers.iter().any(|explorer| explorer.is_eip3091()),
+            websocket_rpc: self.websocket_rpcs().next().is_some(),
+            l2: self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.kind.eq_ignore_ascii_case("L2")),
+        }
+    }
+}
+
+impl ChainRegistry {
+    pub fn capability_matrix(&self) -> CapabilityMatrix {
+        CapabilityMatrix {
+            rows: self
+                .iter()
+                .map(|chain| CapabilityRow {
+                    chain_id: chain.chain_id,
+                    name: chain.display_name().into(),
+                    capabilities: chain.capabilities(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capability_matrix() {
+        let registry = ChainRegistry::global().expect("Should load the chains");
+        let matrix = registry.capability_matrix();
+        assert_eq!(registry.len(), matrix.rows.len());
+
+        let arbitrum = Chain::get(42161).expect("Chain(42161) should exist");
+        assert!(arbitrum.capabilities().l2);
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert!(ethereum.capabilities().ens && !ethereum.capabilities().l2);
+
+        let json = serde_json::from_str::<serde_json::Value>(&matrix.to_json())
+            .expect("Should be valid JSON");
+        assert_eq!(Some(true), json[0]["eip1559"].as_bool());
+
+        let csv = matrix.to_csv();
+        assert_eq!(Some(CSV_HEADER), csv.lines().next());
+        assert_eq!(matrix.rows.len() + 1, csv.lines().count());
+        assert_eq!("\"a, \"\"b\"\"\"", csv_field("a, \"b\""));
+    }
+}
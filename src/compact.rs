@@ -0,0 +1,116 @@
+//! Compact fixed-layout binary descriptors, e.g. for flashing chain metadata
+//! onto hardware wallets.
+//!
+//! Every descriptor is [`DESCRIPTOR_LEN`] bytes long:
+//!
+//! | offset | size | field                                                       |
+//! |--------|------|-------------------------------------------------------------|
+//! | 0      | 8    | chain id, big-endian `u64`                                  |
+//! | 8      | 1    | native currency decimals (saturated to `u8`)                |
+//! | 9      | 7    | native currency symbol, UTF-8, zero padded                  |
+//! | 16     | 16   | [sanitized](crate::Chain::sanitized_name) name, UTF-8, zero padded |
+//!
+//! Strings which don't fit are truncated at a character boundary.
+use crate::Chain;
+
+/// The length in bytes of a single compact descriptor.
+pub const DESCRIPTOR_LEN: usize = 32;
+
+const SYMBOL: std::ops::Range<usize> = 9..16;
+const NAME: std::ops::Range<usize> = 16..32;
+
+impl Chain {
+    /// Encodes the chain as a compact descriptor, see the [module](crate::compact) docs.
+    pub fn to_compact_descriptor(&self) -> [u8; DESCRIPTOR_LEN] {
+        let mut descriptor = [0_u8; DESCRIPTOR_LEN];
+
+        descriptor[..8].copy_from_slice(&self.chain_id.to_be_bytes());
+        descriptor[8] = u8::try_from(self.native_currency.decimals.max(0)).unwrap_or(u8::MAX);
+        write_truncated(&mut descriptor[SYMBOL], &self.native_currency.symbol);
+        write_truncated(&mut descriptor[NAME], &self.sanitized_name());
+
+        descriptor
+    }
+}
+
+/// Concatenates the descriptors of all the given chains.
+pub fn export<'a>(chains: impl IntoIterator<Item = &'a Chain>) -> Vec<u8> {
+    chains
+        .into_iter()
+        .flat_map(|chain| chain.to_compact_descriptor())
+        .collect()
+}
+
+/// A decoded compact descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactDescriptor {
+    pub chain_id: u64,
+    pub decimals: u8,
+    pub symbol: String,
+    pub name: String,
+}
+
+impl CompactDescriptor {
+    pub fn decode(descriptor: &[u8; DESCRIPTOR_LEN]) -> Self {
+        let mut chain_id = [0_u8; 8];
+        chain_id.copy_from_slice(&descriptor[..8]);
+
+        Self {
+            chain_id: u64::from_be_bytes(chain_id),
+            decimals: descriptor[8],
+            symbol: read_padded(&descriptor[SYMBOL]),
+            name: read_padded(&descriptor[NAME]),
+        }
+    }
+}
+
+fn write_truncated(buf: &mut [u8], value: &str) {
+    let mut end = value.len().min(buf.len());
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    buf[..end].copy_from_slice(&value.as_bytes()[..end]);
+}
+
+fn read_padded(buf: &[u8]) -> String {
+    let end = buf.iter().position(|&byte| byte == 0).unwrap_or(buf.len());
+
+    String::from_utf8_lossy(&buf[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_descriptor() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let descriptor = ethereum.to_compact_descriptor();
+
+        assert_eq!(
+            CompactDescriptor {
+                chain_id: 1,
+                decimals: 18,
+                symbol: "ETH".into(),
+                name: "Ethereum Mainnet".into(),
+            },
+            CompactDescriptor::decode(&descriptor)
+        );
+
+        let bnb = Chain::get(56).expect("Chain(56) should exist");
+        let exported = export([&ethereum, &bnb]);
+        assert_eq!(2 * DESCRIPTOR_LEN, exported.len());
+        // "BNB Smart Chain Mainnet" doesn't fit
+        let decoded = CompactDescriptor::decode(exported[DESCRIPTOR_LEN..].try_into().unwrap());
+        assert_eq!("BNB Smart Chain ", decoded.name);
+    }
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        let mut buf = [0_u8; 4];
+        write_truncated(&mut buf, "abcé");
+
+        assert_eq!("abc", read_padded(&buf));
+    }
+}
@@ -0,0 +1,203 @@
+//! Tolerant deserializers for numbers which upstream has historically
+//! encoded as strings (`"56"`) or floats (`18.0`).
+use std::fmt;
+
+use serde::{
+    de::{self, Visitor},
+    Deserializer,
+};
+
+/// 2^64, `u64::MAX as f64` rounds up to it, so it's an exclusive bound
+const U64_BOUND: f64 = 18_446_744_073_709_551_616.0;
+/// 2^63, the exclusive upper bound of `i64`, whose lower bound is -2^63
+const I64_BOUND: f64 = 9_223_372_036_854_775_808.0;
+
+struct TolerantU64;
+
+impl<'de> Visitor<'de> for TolerantU64 {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a non-negative integer, integral float or a string containing one")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<u64, E> {
+        if value.fract() == 0.0 && (0.0..U64_BOUND).contains(&value) {
+            Ok(value as u64)
+        } else {
+            Err(E::invalid_value(de::Unexpected::Float(value), &self))
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+        let trimmed = value.trim();
+
+        match trimmed.parse::<u64>() {
+            Ok(number) => Ok(number),
+            Err(_) => trimmed
+                .parse::<f64>()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+                .and_then(|float| self.visit_f64(float)),
+        }
+    }
+}
+
+/// Deserializes a `u64` from a number or a numeric string.
+pub(crate) fn u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    deserializer.deserialize_any(TolerantU64)
+}
+
+/// Deserializes an optional `u64` from a number or a numeric string.
+pub(crate) fn option_u64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    struct TolerantOption;
+
+    impl<'de> Visitor<'de> for TolerantOption {
+        type Value = Option<u64>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            TolerantU64.expecting(f)
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            u64(deserializer).map(Some)
+        }
+    }
+
+    deserializer.deserialize_option(TolerantOption)
+}
+
+struct TolerantI64;
+
+impl<'de> Visitor<'de> for TolerantI64 {
+    type Value = i64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer fitting in i64, integral float or a string containing one")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<i64, E> {
+        Ok(value)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<i64, E> {
+        i64::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<i64, E> {
+        if value.fract() == 0.0 && (-I64_BOUND..I64_BOUND).contains(&value) {
+            Ok(value as i64)
+        } else {
+            Err(E::invalid_value(de::Unexpected::Float(value), &self))
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<i64, E> {
+        let trimmed = value.trim();
+
+        match trimmed.parse::<i64>() {
+            Ok(number) => Ok(number),
+            Err(_) => trimmed
+                .parse::<f64>()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+                .and_then(|float| self.visit_f64(float)),
+        }
+    }
+}
+
+/// Deserializes an `i64` from a number or a numeric string.
+pub(crate) fn i64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    deserializer.deserialize_any(TolerantI64)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Numbers {
+        #[serde(deserialize_with = "super::u64")]
+        id: u64,
+        #[serde(default, deserialize_with = "super::option_u64")]
+        slip44: Option<u64>,
+        #[serde(deserialize_with = "super::i64")]
+        decimals: i64,
+    }
+
+    #[test]
+    fn tolerant_numbers() {
+        let expected = Numbers {
+            id: 56,
+            slip44: Some(714),
+            decimals: 18,
+        };
+
+        for value in [
+            json!({ "id": 56, "slip44": 714, "decimals": 18 }),
+            json!({ "id": "56", "slip44": " 714 ", "decimals": 18.0 }),
+            json!({ "id": 56.0, "slip44": "714", "decimals": "18" }),
+        ] {
+            assert_eq!(expected, serde_json::from_value::<Numbers>(value).unwrap());
+        }
+
+        let missing =
+            serde_json::from_value::<Numbers>(json!({ "id": 1, "decimals": 18 })).unwrap();
+        assert_eq!(None, missing.slip44);
+        let null =
+            serde_json::from_value::<Numbers>(json!({ "id": 1, "slip44": null, "decimals": 18 }))
+                .unwrap();
+        assert_eq!(None, null.slip44);
+    }
+
+    #[test]
+    fn rejects_non_integers() {
+        for value in [
+            json!({ "id": 1.5, "decimals": 18 }),
+            json!({ "id": -1, "decimals": 18 }),
+            json!({ "id": "one", "decimals": 18 }),
+        ] {
+            assert!(serde_json::from_value::<Numbers>(value).is_err());
+        }
+    }
+
+    #[test]
+    fn number_bounds() {
+        for decimals in [json!(-1), json!("-1"), json!(-1.0)] {
+            let numbers =
+                serde_json::from_value::<Numbers>(json!({ "id": 1, "decimals": decimals }))
+                    .expect("Negative decimals should deserialize");
+            assert_eq!(-1, numbers.decimals);
+        }
+
+        // 2^64 and 2^63 don't fit
+        assert!(serde_json::from_value::<Numbers>(
+            json!({ "id": 18_446_744_073_709_551_616.0, "decimals": 18 })
+        )
+        .is_err());
+        assert!(serde_json::from_value::<Numbers>(
+            json!({ "id": 1, "decimals": 9_223_372_036_854_775_808.0 })
+        )
+        .is_err());
+    }
+}
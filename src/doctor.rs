@@ -0,0 +1,116 @@
+//! Startup self-checks of the registry, see [`ChainRegistry::self_check`].
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{embedded, ChainRegistry};
+
+/// Chains which every build of the data is expected to have:
+/// Ethereum, BNB Smart Chain and Polygon.
+pub const ANCHOR_CHAIN_IDS: [u64; 3] = [1, 56, 137];
+
+/// Serializes as `{"passed":true,"checks":[{"name":"anchors","passed":true}, ...]}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelfCheckReport {
+    pub passed: bool,
+    pub checks: Vec<Check>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    /// E.g. `anchors`
+    pub name: &'static str,
+    pub passed: bool,
+    /// Why the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Check {
+    fn new(name: &'static str, failure: Option<String>) -> Self {
+        Self {
+            name,
+            passed: failure.is_none(),
+            detail: failure,
+        }
+    }
+}
+
+impl ChainRegistry {
+    /// Runs the curated checks, meant to be called at startup:
+    ///
+    /// - `anchors`: the [`ANCHOR_CHAIN_IDS`] are present, which fails for
+    ///   builds filtered with `EVM_CHAINS_FILTER` excluding them
+    /// - `unique_short_names`: no two chains share a short name
+    /// - `embedded_digest`: the chain files compiled into the binary hash to
+    ///   the digest `build.rs` computed when it read them, which fails e.g.
+    ///   for files changed between the build script and the compilation
+    pub fn self_check(&self) -> SelfCheckReport {
+        let missing = ANCHOR_CHAIN_IDS
+            .iter()
+            .filter(|&&chain_id| self.get(chain_id).is_none())
+            .collect::<Vec<_>>();
+        let anchors = Check::new(
+            "anchors",
+            (!missing.is_empty()).then(|| format!("missing chains {:?}", missing)),
+        );
+
+        let mut short_names = HashMap::<&str, Vec<u64>>::new();
+        for chain in self.iter() {
+            short_names
+                .entry(&chain.short_name)
+                .or_default()
+                .push(chain.chain_id);
+        }
+        let mut duplicates = short_names
+            .into_iter()
+            .filter(|(_, chain_ids)| chain_ids.len() > 1)
+            .map(|(short_name, chain_ids)| format!("`{}` {:?}", short_name, chain_ids))
+            .collect::<Vec<_>>();
+        duplicates.sort_unstable();
+        let unique_short_names = Check::new(
+            "unique_short_names",
+            (!duplicates.is_empty()).then(|| format!("duplicates {}", duplicates.join(", "))),
+        );
+
+        let embedded_digest = Check::new(
+            "embedded_digest",
+            (!embedded::digest_matches())
+                .then(|| "embedded chain files differ from the ones read by the build".into()),
+        );
+
+        let checks = vec![anchors, unique_short_names, embedded_digest];
+        SelfCheckReport {
+            passed: checks.iter().all(|check| check.passed),
+            checks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_check() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        let report = registry.self_check();
+
+        assert!(report.passed, "{:?}", report);
+        assert_eq!(
+            serde_json::json!({ "name": "anchors", "passed": true }),
+            serde_json::to_value(&report.checks[0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_anchors() {
+        let registry = ChainRegistry::try_load()
+            .expect("Should load the embedded chains")
+            .retain(|chain| chain.chain_id != 56);
+        let report = registry.self_check();
+
+        assert!(!report.passed);
+        assert_eq!(Some("missing chains [56]".into()), report.checks[0].detail);
+    }
+}
@@ -0,0 +1,91 @@
+//! Egress (outbound traffic) allowlists for locked-down environments.
+use std::{collections::BTreeSet, fmt};
+
+use crate::{url, Chain, ChainRegistry};
+
+/// A host and port the application may contact.
+///
+/// Displayed as `host:port`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HostPattern {
+    pub host: String,
+    pub port: u16,
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+impl HostPattern {
+    fn from_url(url: &str) -> Option<Self> {
+        let host = url::host(url)?;
+        // hosts with template variables (`${API_KEY}`) can't be turned into a rule
+        if host.contains('$') {
+            return None;
+        }
+
+        Some(Self {
+            host: host.to_ascii_lowercase(),
+            port: url::port(url)?,
+        })
+    }
+}
+
+impl Chain {
+    /// All the hosts of the [`Chain::rpc_urls`], [`Chain::explorer_urls`] and
+    /// [`Chain::faucet_urls`] of the chain.
+    pub fn egress_hosts(&self) -> BTreeSet<HostPattern> {
+        self.rpc_urls()
+            .chain(self.explorer_urls())
+            .chain(self.faucet_urls())
+            .filter_map(HostPattern::from_url)
+            .collect()
+    }
+}
+
+impl ChainRegistry {
+    /// The sorted and deduplicated [`Chain::egress_hosts`] of the given chain ids.
+    ///
+    /// Unknown chain ids are ignored.
+    pub fn egress_allowlist(&self, chain_ids: impl IntoIterator<Item = u64>) -> Vec<HostPattern> {
+        chain_ids
+            .into_iter()
+            .filter_map(|chain_id| self.get(chain_id))
+            .flat_map(Chain::egress_hosts)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// See [`ChainRegistry::egress_allowlist`].
+pub fn egress_allowlist(chain_ids: impl IntoIterator<Item = u64>) -> Vec<HostPattern> {
+    ChainRegistry::global()
+        .map(|registry| registry.egress_allowlist(chain_ids))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowlist() {
+        let allowlist = egress_allowlist([1, 137, u64::MAX]);
+
+        let etherscan = HostPattern {
+            host: "etherscan.io".into(),
+            port: 443,
+        };
+        assert!(allowlist.contains(&etherscan));
+        assert!(allowlist.iter().any(|host| host.host == "polygonscan.com"));
+        // sorted & deduplicated
+        assert!(allowlist.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!("etherscan.io:443", etherscan.to_string());
+
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        assert_eq!(allowlist, registry.egress_allowlist([137, 1]));
+    }
+}
@@ -0,0 +1,73 @@
+//! The chain and icon files embedded at build time, see `build.rs`.
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/chains.rs"));
+    include!(concat!(env!("OUT_DIR"), "/icons.rs"));
+}
+
+use generated::{CHAIN_FILES, DIGEST, ICON_FILES};
+
+use crate::sha256::sha256;
+
+/// `(chain id, chain file json)`, sorted by chain id.
+pub(crate) fn chain_files() -> impl ExactSizeIterator<Item = (u64, &'static str)> {
+    CHAIN_FILES.iter().copied()
+}
+
+/// The json of the `eip155-CHAIN_ID.json` chain file.
+pub(crate) fn chain_file(chain_id: u64) -> Option<&'static str> {
+    CHAIN_FILES
+        .binary_search_by_key(&chain_id, |&(chain_id, _)| chain_id)
+        .ok()
+        .map(|index| CHAIN_FILES[index].1)
+}
+
+/// The json of the `_data/icons/NAME.json` icon file.
+pub(crate) fn icon_file(name: &str) -> Option<&'static str> {
+    ICON_FILES
+        .binary_search_by_key(&name, |&(name, _)| name)
+        .ok()
+        .map(|index| ICON_FILES[index].1)
+}
+
+/// The digest of the chain files, framed like the [`DIGEST`] computed by `build.rs`.
+pub(crate) fn digest<'a>(files: impl IntoIterator<Item = (u64, &'a str)>) -> [u8; 32] {
+    let mut embedded = Vec::new();
+    for (chain_id, json) in files {
+        embedded.extend(chain_id.to_be_bytes());
+        embedded.extend((json.len() as u64).to_be_bytes());
+        embedded.extend(json.as_bytes());
+    }
+
+    sha256(&embedded)
+}
+
+/// Whether the embedded chain files still hash to the [`DIGEST`] computed
+/// by `build.rs` when it read them.
+pub(crate) fn digest_matches() -> bool {
+    digest(chain_files()) == DIGEST
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_chain_files() {
+        assert!(CHAIN_FILES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(chain_file(1).is_some_and(|json| json.contains("\"chainId\"")));
+        assert_eq!(None, chain_file(u64::MAX));
+        assert!(digest_matches());
+    }
+
+    #[test]
+    fn tampered_chain_files() {
+        let tampered = chain_files().map(|(chain_id, json)| match chain_id {
+            1 => (chain_id, "{}"),
+            _ => (chain_id, json),
+        });
+        assert_ne!(DIGEST, digest(tampered));
+
+        let dropped = chain_files().filter(|&(chain_id, _)| chain_id != 1);
+        assert_ne!(DIGEST, digest(dropped));
+    }
+}
@@ -0,0 +1,615 @@
+//! Block explorer helpers.
+use std::{borrow::Cow, collections::HashMap, fmt, sync::Arc};
+
+use crate::{
+    error, registry, url, Chain, ChainRegistry, Error, Explorer, ExplorerStandard, RegistryBuilder,
+};
+
+/// Explorer hosts which moved, from the former host to the current one.
+const BUNDLED_REDIRECTS: &[(&str, &str)] = &[("explorer.optimism.io", "optimistic.etherscan.io")];
+
+/// More redirect hops than this are treated as a loop in the overlay.
+const MAX_REDIRECTS: usize = 8;
+
+/// The Etherscan V2 multichain API, the chain is selected with the `chainid` parameter
+const ETHERSCAN_V2_API: &str = "https://api.etherscan.io/v2/api";
+
+/// Explorer hosts served by Etherscan, which all support the
+/// [Etherscan V2](https://docs.etherscan.io/etherscan-v2) multichain API,
+/// as of 2025.
+///
+/// Hosts which have moved off Etherscan (e.g. `ftmscan.com` to Sonic,
+/// `cronoscan.com` to `explorer.cronos.org`) are left out.
+const ETHERSCAN_HOSTS: &[&str] = &[
+    "etherscan.io",
+    "bscscan.com",
+    "polygonscan.com",
+    "arbiscan.io",
+    "basescan.org",
+    "gnosisscan.io",
+    "moonscan.io",
+    "celoscan.io",
+    "lineascan.build",
+    "scrollscan.com",
+    "blastscan.io",
+    "apescan.io",
+];
+
+/// The request shapes an explorer API accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiFlavor {
+    /// Etherscan family explorer, reachable through the V2 multichain API
+    EtherscanV2,
+    /// Blockscout instance with the `/api/v2` REST API
+    BlockscoutV2,
+    Unknown,
+}
+
+impl Explorer {
+    /// Guesses the explorer API from well known hosts and names.
+    ///
+    /// This is a heuristic, the explorer isn't probed: hosts which changed
+    /// their API since the crate's list of Etherscan hosts was last updated are
+    /// classified wrongly and self-hosted instances may be [`ApiFlavor::Unknown`].
+    pub fn api_flavor(&self) -> ApiFlavor {
+        let host = url::host(&self.url).unwrap_or_default();
+
+        if ETHERSCAN_HOSTS
+            .iter()
+            .any(|etherscan| url::is_same_or_subdomain(host, etherscan))
+        {
+            ApiFlavor::EtherscanV2
+        } else if host.to_ascii_lowercase().contains("blockscout")
+            || self.name.to_ascii_lowercase().contains("blockscout")
+        {
+            ApiFlavor::BlockscoutV2
+        } else {
+            ApiFlavor::Unknown
+        }
+    }
+
+    /// Whether the explorer follows [EIP-3091](https://eips.ethereum.org/EIPS/eip-3091),
+    /// i.e. it serves the `/tx`, `/address`, `/block` and `/token` paths.
+    pub fn is_eip3091(&self) -> bool {
+        self.standard == ExplorerStandard::Eip3091
+    }
+
+    /// E.g. `https://etherscan.io/tx/0x...`, `None` unless [`Explorer::is_eip3091`].
+    pub fn tx_url(&self, hash: &str) -> Option<String> {
+        self.eip3091_url("tx", hash)
+    }
+
+    /// E.g. `https://etherscan.io/address/0x...`, `None` unless [`Explorer::is_eip3091`].
+    pub fn address_url(&self, address: &str) -> Option<String> {
+        self.eip3091_url("address", address)
+    }
+
+    /// E.g. `https://etherscan.io/block/17000000`, `None` unless [`Explorer::is_eip3091`].
+    pub fn block_url(&self, number: u64) -> Option<String> {
+        self.eip3091_url("block", &number.to_string())
+    }
+
+    /// E.g. `https://etherscan.io/token/0x...`, `None` unless [`Explorer::is_eip3091`].
+    pub fn token_url(&self, address: &str) -> Option<String> {
+        self.eip3091_url("token", address)
+    }
+
+    fn eip3091_url(&self, path: &str, value: &str) -> Option<String> {
+        self.is_eip3091()
+            .then(|| format!("{}/{}/{}", self.url.trim_end_matches('/'), path, value))
+    }
+}
+
+/// The [`BUNDLED_REDIRECTS`].
+pub(crate) fn bundled_redirects() -> HashMap<String, String> {
+    BUNDLED_REDIRECTS
+        .iter()
+        .map(|&(from, to)| (from.to_string(), to.to_string()))
+        .collect()
+}
+
+impl RegistryBuilder {
+    /// Replaces the bundled explorer host migrations, from the former
+    /// lowercase host to the current one, followed by
+    /// [`ChainRegistry::canonical_url`].
+    pub fn explorer_redirects(mut self, overlay: HashMap<String, String>) -> Self {
+        self.overlays.explorer_redirects = overlay;
+        self
+    }
+
+    /// The shortener of the `Chain::*_link` builders of the registry's
+    /// chains, none by default.
+    pub fn link_shortener(mut self, shortener: impl LinkShortener + 'static) -> Self {
+        self.overlays.link_shortener = Some(Arc::new(shortener));
+        self
+    }
+}
+
+/// Parses an explorer redirects overlay, a JSON object of former hosts to
+/// current ones, e.g. `{ "explorer.optimism.io": "optimistic.etherscan.io" }`.
+pub fn explorer_redirects_from_json(json: &str) -> Result<HashMap<String, String>, Error> {
+    serde_json::from_str(json).map_err(error::deserialize)
+}
+
+impl ChainRegistry {
+    /// The explorer host migrations, see [`RegistryBuilder::explorer_redirects`].
+    pub fn explorer_redirects(&self) -> &HashMap<String, String> {
+        &self.overlays().explorer_redirects
+    }
+
+    /// The shortener of the explorer links, see [`RegistryBuilder::link_shortener`].
+    pub fn link_shortener(&self) -> Option<&dyn LinkShortener> {
+        self.overlays().link_shortener.as_deref()
+    }
+
+    /// Rewrites the host of an explorer `url` (e.g. a stored deep link) following
+    /// the [`ChainRegistry::explorer_redirects`], keeping the path and query.
+    ///
+    /// Unknown hosts are returned as they are, as are urls whose redirects loop.
+    pub fn canonical_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        canonical_url_with(self.explorer_redirects(), url)
+    }
+}
+
+/// See [`ChainRegistry::explorer_redirects`].
+pub fn explorer_redirects() -> &'static HashMap<String, String> {
+    &registry::overlays().explorer_redirects
+}
+
+/// See [`ChainRegistry::canonical_url`].
+pub fn canonical_url(url: &str) -> Cow<'_, str> {
+    canonical_url_with(explorer_redirects(), url)
+}
+
+fn canonical_url_with<'a>(redirects: &HashMap<String, String>, url: &'a str) -> Cow<'a, str> {
+    let mut canonical = Cow::Borrowed(url);
+
+    for _ in 0..MAX_REDIRECTS {
+        let redirect = url::host(&canonical)
+            .and_then(|host| redirects.get(&host.to_ascii_lowercase()))
+            .and_then(|to| url::replace_host(&canonical, to));
+
+        match redirect {
+            Some(redirected) => canonical = Cow::Owned(redirected),
+            None => return canonical,
+        }
+    }
+
+    Cow::Borrowed(url)
+}
+
+impl Chain {
+    /// The [`Explorer::url`]s after the explorer host migrations of the
+    /// registry the chain was loaded by, see [`ChainRegistry::canonical_url`].
+    pub fn canonical_explorer_urls(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        self.explorers
+            .iter()
+            .map(|explorer| canonical_url_with(&self.overlays.explorer_redirects, &explorer.url))
+    }
+}
+
+impl Explorer {
+    /// The [`Explorer::url`] after the host migrations of the
+    /// [`ChainRegistry::global`] registry, see [`canonical_url`].
+    ///
+    /// Prefer [`Chain::canonical_explorer_urls`], which follows the migrations
+    /// of the registry the chain was loaded by.
+    pub fn canonical_url(&self) -> Cow<'_, str> {
+        canonical_url(&self.url)
+    }
+}
+
+/// Shortens explorer links, e.g. for notifications sent over SMS or chat.
+pub trait LinkShortener: Send + Sync {
+    /// The short link for `url`, `None` if it can't be shortened.
+    fn shorten(&self, url: &str) -> Option<String>;
+}
+
+impl<F> LinkShortener for F
+where
+    F: Fn(&str) -> Option<String> + Send + Sync,
+{
+    fn shorten(&self, url: &str) -> Option<String> {
+        self(url)
+    }
+}
+
+impl fmt::Debug for dyn LinkShortener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LinkShortener")
+    }
+}
+
+/// An explorer link with its short form, if the registry of the chain has a
+/// [`LinkShortener`] and it could shorten the link.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplorerLink {
+    pub url: String,
+    pub short: Option<String>,
+}
+
+impl ExplorerLink {
+    /// Shortens the `url` with the given shortener.
+    pub fn with_shortener(url: String, shortener: &dyn LinkShortener) -> Self {
+        let short = shortener.shorten(&url);
+
+        Self { url, short }
+    }
+
+    /// The short link when there is one, otherwise the full url.
+    pub fn as_str(&self) -> &str {
+        self.short.as_deref().unwrap_or(&self.url)
+    }
+}
+
+impl fmt::Display for ExplorerLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An endpoint for submitting contract sources for verification.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VerificationApi {
+    /// An Etherscan-compatible endpoint, the sources are `POST`ed to the url
+    /// which already has the `module=contract&action=verifysourcecode` query.
+    EtherscanCompatible { url: String },
+    /// A Sourcify server, see [`Chain::sourcify_supported`].
+    Sourcify { server: String, chain_id: u64 },
+}
+
+impl Chain {
+    /// The contract verification endpoint of the chain, where known.
+    ///
+    /// Etherscan explorers are preferred over Blockscout instances, with
+    /// [Sourcify](crate::sourcify) as a fallback.
+    pub fn verification_api(&self) -> Option<VerificationApi> {
+        let by_flavor = |flavor| {
+            self.explorers
+                .iter()
+                .find(|explorer| explorer.api_flavor() == flavor)
+        };
+
+        if by_flavor(ApiFlavor::EtherscanV2).is_some() {
+            return Some(VerificationApi::EtherscanCompatible {
+                url: format!(
+                    "{}?chainid={}&module=contract&action=verifysourcecode",
+                    ETHERSCAN_V2_API, self.chain_id
+                ),
+            });
+        }
+
+        if let Some(blockscout) = by_flavor(ApiFlavor::BlockscoutV2) {
+            return Some(VerificationApi::EtherscanCompatible {
+                url: format!(
+                    "{}/api?module=contract&action=verifysourcecode",
+                    blockscout.url.trim_end_matches('/')
+                ),
+            });
+        }
+
+        self.sourcify_supported()
+            .then(|| VerificationApi::Sourcify {
+                server: crate::sourcify::SOURCIFY_SERVER.into(),
+                chain_id: self.chain_id,
+            })
+    }
+}
+
+impl Chain {
+    /// The explorer to link to, the first one which is both
+    /// [EIP-3091](https://eips.ethereum.org/EIPS/eip-3091) compliant and on an
+    /// `https` url with a host.
+    ///
+    /// Falls back, in this order, to the first EIP-3091 explorer, the first
+    /// `https` one and finally the first explorer. Only the explorers allowed
+    /// by the url policy of the registry the chain was loaded by are
+    /// considered, like for [`Chain::explorer_urls`].
+    pub fn primary_explorer(&self) -> Option<&Explorer> {
+        let https = |explorer: &&Explorer| {
+            explorer
+                .url
+                .get(..8)
+                .is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://"))
+                && url::host(&explorer.url).is_some()
+        };
+
+        let mut allowed = self
+            .explorers
+            .iter()
+            .filter(|explorer| self.overlays.url_policy.allows(&explorer.url));
+        let mut eip3091 = allowed.clone().filter(|explorer| explorer.is_eip3091());
+        eip3091
+            .clone()
+            .find(https)
+            .or_else(|| eip3091.next())
+            .or_else(|| allowed.clone().find(https))
+            .or_else(|| allowed.next())
+    }
+}
+
+/// Links on the [`Chain::primary_explorer`].
+impl Chain {
+    pub fn tx_url(&self, hash: &str) -> Option<String> {
+        self.primary_explorer()?.tx_url(hash)
+    }
+
+    pub fn address_url(&self, address: &str) -> Option<String> {
+        self.primary_explorer()?.address_url(address)
+    }
+
+    pub fn block_url(&self, number: u64) -> Option<String> {
+        self.primary_explorer()?.block_url(number)
+    }
+
+    pub fn token_url(&self, address: &str) -> Option<String> {
+        self.primary_explorer()?.token_url(address)
+    }
+}
+
+/// Like the `Chain::*_url` builders, shortened by the [`LinkShortener`] of
+/// the registry the chain was loaded by, see [`RegistryBuilder::link_shortener`].
+impl Chain {
+    pub fn tx_link(&self, hash: &str) -> Option<ExplorerLink> {
+        self.tx_url(hash).map(|url| self.link(url))
+    }
+
+    pub fn address_link(&self, address: &str) -> Option<ExplorerLink> {
+        self.address_url(address).map(|url| self.link(url))
+    }
+
+    pub fn block_link(&self, number: u64) -> Option<ExplorerLink> {
+        self.block_url(number).map(|url| self.link(url))
+    }
+
+    pub fn token_link(&self, address: &str) -> Option<ExplorerLink> {
+        self.token_url(address).map(|url| self.link(url))
+    }
+
+    fn link(&self, url: String) -> ExplorerLink {
+        match &self.overlays.link_shortener {
+            Some(shortener) => ExplorerLink::with_shortener(url, &**shortener),
+            None => ExplorerLink { url, short: None },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{policy::UrlPolicy, Chain};
+
+    #[test]
+    fn api_flavors() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let etherscan = ethereum
+            .explorers
+            .iter()
+            .find(|explorer| explorer.name == "etherscan")
+            .expect("Etherscan should be an explorer of Chain(1)");
+        assert_eq!(ApiFlavor::EtherscanV2, etherscan.api_flavor());
+        assert!(ethereum
+            .explorers
+            .iter()
+            .any(|explorer| explorer.icon.as_deref() == Some("blockscout")));
+
+        let explorer = |name: &str, url: &str| Explorer {
+            name: name.into(),
+            url: url.into(),
+            standard: ExplorerStandard::Eip3091,
+            icon: None,
+        };
+        assert_eq!(
+            ApiFlavor::EtherscanV2,
+            explorer("etherscan-goerli", "https://goerli.etherscan.io").api_flavor()
+        );
+        assert_eq!(
+            ApiFlavor::BlockscoutV2,
+            explorer("blockscout", "https://explorer.example.com").api_flavor()
+        );
+        assert_eq!(
+            ApiFlavor::BlockscoutV2,
+            explorer("Explorer", "https://eth.blockscout.com").api_flavor()
+        );
+        assert_eq!(
+            ApiFlavor::Unknown,
+            explorer("dexguru", "https://ethereum.dex.guru").api_flavor()
+        );
+    }
+
+    #[test]
+    fn eip3091_links() {
+        let explorer = Explorer {
+            name: "etherscan".into(),
+            url: "https://etherscan.io/".into(),
+            standard: ExplorerStandard::Eip3091,
+            icon: None,
+        };
+        assert_eq!(
+            Some("https://etherscan.io/tx/0xabc".to_string()),
+            explorer.tx_url("0xabc")
+        );
+        assert_eq!(
+            Some("https://etherscan.io/block/17000000".to_string()),
+            explorer.block_url(17_000_000)
+        );
+
+        let non_standard = Explorer {
+            standard: ExplorerStandard::None,
+            ..explorer
+        };
+        assert_eq!(None, non_standard.address_url("0xabc"));
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert!(ethereum
+            .token_url("0xabc")
+            .is_some_and(|url| url.ends_with("/token/0xabc")));
+    }
+
+    #[test]
+    fn redirected_urls() {
+        assert_eq!(
+            "https://optimistic.etherscan.io/tx/0xabc",
+            canonical_url("https://Explorer.Optimism.io/tx/0xabc")
+        );
+        assert!(matches!(
+            canonical_url("https://etherscan.io/tx/0xabc"),
+            Cow::Borrowed("https://etherscan.io/tx/0xabc")
+        ));
+
+        let overlay = explorer_redirects_from_json(r#"{ "a.example": "b.example" }"#)
+            .expect("Should deserialize explorer redirects");
+        assert_eq!("b.example", overlay["a.example"]);
+
+        let registry = ChainRegistry::builder()
+            .explorer_redirects(overlay)
+            .load()
+            .expect("Should load the embedded chains");
+        assert_eq!(
+            "https://b.example/tx",
+            registry.canonical_url("https://a.example/tx")
+        );
+        assert_eq!(
+            "https://explorer.optimism.io/tx",
+            registry.canonical_url("https://explorer.optimism.io/tx")
+        );
+
+        let mut chain = registry.get(1).cloned().expect("Chain(1) should exist");
+        chain.explorers.truncate(1);
+        chain.explorers[0].url = "https://a.example".into();
+        assert_eq!(
+            vec!["https://b.example"],
+            chain.canonical_explorer_urls().collect::<Vec<_>>()
+        );
+        assert_eq!("https://a.example", chain.explorers[0].canonical_url());
+    }
+
+    #[test]
+    fn primary_explorers() {
+        let explorer = |name: &str, url: &str, standard: &str| Explorer {
+            name: name.into(),
+            url: url.into(),
+            standard: ExplorerStandard::from_name(standard),
+            icon: None,
+        };
+        let mut chain = Chain::get(1).expect("Chain(1) should exist");
+
+        chain.explorers = vec![
+            explorer("plain", "http://plain.example", "none"),
+            explorer("http", "http://http.example", "EIP3091"),
+            explorer("https", "https://https.example", "EIP3091"),
+        ];
+        assert_eq!(
+            Some("https"),
+            chain.primary_explorer().map(|e| e.name.as_str())
+        );
+
+        chain.explorers.pop();
+        assert_eq!(
+            Some("http"),
+            chain.primary_explorer().map(|e| e.name.as_str())
+        );
+
+        chain.explorers = vec![
+            explorer("plain", "http://plain.example", "none"),
+            explorer("secure", "https://secure.example", "none"),
+        ];
+        assert_eq!(
+            Some("secure"),
+            chain.primary_explorer().map(|e| e.name.as_str())
+        );
+        assert_eq!(None, chain.tx_url("0xabc"));
+
+        chain.explorers.clear();
+        assert_eq!(None, chain.primary_explorer());
+
+        let registry = ChainRegistry::builder()
+            .url_policy(UrlPolicy::TLS_ONLY)
+            .load()
+            .expect("Should load the embedded chains");
+        let mut chain = registry.get(1).cloned().expect("Chain(1) should exist");
+        chain.explorers = vec![
+            explorer("http", "http://http.example", "EIP3091"),
+            explorer("plain", "http://plain.example", "none"),
+        ];
+        assert_eq!(None, chain.primary_explorer());
+        assert_eq!(None, chain.tx_url("0xabc"));
+        assert_eq!(None, chain.address_url("0xabc"));
+    }
+
+    #[test]
+    fn shortened_links() {
+        let shortener = |url: &str| {
+            url.strip_prefix("https://")
+                .map(|url| format!("s.example/{}", url.len()))
+        };
+
+        let link = ExplorerLink::with_shortener("https://etherscan.io/tx/0xabc".into(), &shortener);
+        assert_eq!("https://etherscan.io/tx/0xabc", link.url);
+        assert_eq!("s.example/21", link.to_string());
+
+        let failed = ExplorerLink::with_shortener("ipfs://Qm".into(), &shortener);
+        assert_eq!("ipfs://Qm", failed.as_str());
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert_eq!(
+            ethereum.tx_url("0xabc"),
+            ethereum.tx_link("0xabc").map(|link| link.url)
+        );
+        assert_eq!(None, ethereum.tx_link("0xabc").and_then(|link| link.short));
+
+        let registry = ChainRegistry::builder()
+            .link_shortener(shortener)
+            .load()
+            .expect("Should load the embedded chains");
+        assert!(registry.link_shortener().is_some());
+        let link = registry
+            .get(1)
+            .and_then(|ethereum| ethereum.tx_link("0xabc"))
+            .expect("Chain(1) should have an explorer");
+        assert_eq!(ethereum.tx_url("0xabc"), Some(link.url));
+        assert!(link
+            .short
+            .is_some_and(|short| short.starts_with("s.example/")));
+    }
+
+    #[test]
+    fn verification_apis() {
+        let polygon = Chain::get(137).expect("Chain(137) should exist");
+        assert_eq!(
+            Some(VerificationApi::EtherscanCompatible {
+                url: "https://api.etherscan.io/v2/api?chainid=137&module=contract&action=verifysourcecode".into()
+            }),
+            polygon.verification_api()
+        );
+
+        let mut blockscout_only = polygon.clone();
+        blockscout_only.explorers = vec![Explorer {
+            name: "blockscout".into(),
+            url: "https://polygon.blockscout.com/".into(),
+            standard: ExplorerStandard::Eip3091,
+            icon: None,
+        }];
+        assert_eq!(
+            Some(VerificationApi::EtherscanCompatible {
+                url: "https://polygon.blockscout.com/api?module=contract&action=verifysourcecode"
+                    .into()
+            }),
+            blockscout_only.verification_api()
+        );
+
+        let mut sourcify_only = polygon;
+        sourcify_only.explorers.clear();
+        assert_eq!(
+            Some(VerificationApi::Sourcify {
+                server: "https://sourcify.dev/server".into(),
+                chain_id: 137
+            }),
+            sourcify_only.verification_api()
+        );
+
+        let mut unknown = sourcify_only;
+        unknown.chain_id = u64::MAX;
+        assert_eq!(None, unknown.verification_api());
+    }
+}
@@ -0,0 +1,178 @@
+//! Application specific metadata attached to the chains while loading them.
+//!
+//! ```
+//! use evm_chains::{ext::ChainExtensions, Chain, ChainRegistry};
+//!
+//! struct SupportTier(u8);
+//!
+//! let enricher = |chain: &Chain, ext: &mut ChainExtensions| {
+//!     ext.insert(SupportTier(if chain.chain_id == 1 { 1 } else { 3 }));
+//! };
+//! let registry = ChainRegistry::builder()
+//!     .enricher(enricher)
+//!     .load()
+//!     .expect("Should load the chains");
+//!
+//! let ethereum = registry.get(1).expect("Chain(1) should exist");
+//! assert_eq!(Some(1), ethereum.ext::<SupportTier>().map(|tier| tier.0));
+//! ```
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+};
+
+use crate::{Chain, ChainRegistry};
+
+/// Invoked for every chain while loading a [`ChainRegistry`], see
+/// [`RegistryBuilder::enricher`](crate::RegistryBuilder::enricher).
+///
+/// To enrich the chains of [`Chain::get`], set a registry loaded with the
+/// enrichers as the [`ChainRegistry::set_global`] one.
+pub trait Enricher: Send + Sync {
+    fn enrich(&self, chain: &Chain, ext: &mut ChainExtensions);
+}
+
+impl<F> Enricher for F
+where
+    F: Fn(&Chain, &mut ChainExtensions) + Send + Sync,
+{
+    fn enrich(&self, chain: &Chain, ext: &mut ChainExtensions) {
+        self(chain, ext)
+    }
+}
+
+/// Values attached to a chain, at most one per type.
+///
+/// Extensions are not part of the chain data: they are not serialized and
+/// don't take part in the equality of chains.
+#[derive(Clone, Default)]
+pub struct ChainExtensions {
+    values: HashMap<TypeId, Extension>,
+}
+
+#[derive(Clone)]
+struct Extension {
+    type_name: &'static str,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+impl ChainExtensions {
+    /// Attaches the value, replacing the one with the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) {
+        self.values.insert(
+            TypeId::of::<T>(),
+            Extension {
+                type_name: std::any::type_name::<T>(),
+                value: Arc::new(value),
+            },
+        );
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|extension| extension.value.downcast_ref())
+    }
+
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Detaches the value of the type, returning whether there was one.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> bool {
+        self.values.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// The number of attached values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Debug for ChainExtensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.values.values().map(|extension| extension.type_name))
+            .finish()
+    }
+}
+
+impl PartialEq for ChainExtensions {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Chain {
+    /// The value of the type attached by an [`Enricher`].
+    pub fn ext<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.ext.get()
+    }
+
+    /// All the values attached by the [`Enricher`]s.
+    pub fn extensions(&self) -> &ChainExtensions {
+        &self.ext
+    }
+}
+
+impl ChainRegistry {
+    /// The chains with a value of the type attached, in ascending chain id
+    /// order, instead of a side table keyed by chain id.
+    pub fn with_ext<T: Any + Send + Sync>(&self) -> impl Iterator<Item = (&Chain, &T)> {
+        self.iter()
+            .filter_map(|chain| Some((chain, chain.ext::<T>()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct InternalId(&'static str);
+
+    #[test]
+    fn enrich_chains() {
+        let enricher = |chain: &Chain, ext: &mut ChainExtensions| {
+            if chain.chain_id == 1 {
+                ext.insert(InternalId("ethereum"));
+            }
+        };
+        let registry = ChainRegistry::try_load_with(&[Arc::new(enricher)])
+            .expect("Should load the embedded chains");
+
+        let ethereum = registry.get(1).expect("Chain(1) should exist");
+        assert_eq!(Some(&InternalId("ethereum")), ethereum.ext());
+        assert_eq!(None, ethereum.ext::<u64>());
+        assert_eq!(None, registry.get(137).and_then(Chain::ext::<InternalId>));
+        // extensions are not part of the chain data
+        assert_eq!(Chain::get(1).as_ref(), Some(ethereum));
+
+        assert_eq!(
+            vec![1],
+            registry
+                .with_ext::<InternalId>()
+                .map(|(chain, _)| chain.chain_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn extensions() {
+        let mut extensions = ChainExtensions::default();
+        extensions.insert(InternalId("first"));
+        extensions.insert(InternalId("second"));
+        extensions.insert(7_u8);
+
+        assert_eq!(2, extensions.len());
+        assert_eq!(Some(&InternalId("second")), extensions.get());
+        assert!(extensions.remove::<u8>());
+        assert!(!extensions.contains::<u8>());
+    }
+}
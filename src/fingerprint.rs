@@ -0,0 +1,118 @@
+//! Stable fingerprints of the chain data.
+//!
+//! Fingerprints are SHA-256 digests of the canonical JSON of a chain, i.e.
+//! compact JSON with object keys sorted, so two processes agree on the
+//! fingerprint when they hold the same [`Chain`] data.
+//!
+//! Only the modeled fields are covered: chain files which differ in fields
+//! the [`Chain`] doesn't deserialize (see [`crate::canary`]) have the same
+//! fingerprint.
+use serde_json::Value;
+
+use crate::{sha256::sha256, Chain, ChainRegistry};
+
+impl Chain {
+    /// The SHA-256 digest of the canonical JSON of the chain.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        sha256(&canonical_json(self))
+    }
+}
+
+impl ChainRegistry {
+    /// The SHA-256 digest of the fingerprints of all the chains, in ascending
+    /// chain id order.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let fingerprints = self.iter().flat_map(Chain::fingerprint).collect::<Vec<_>>();
+
+        sha256(&fingerprints)
+    }
+}
+
+/// The fingerprint of the [`ChainRegistry::global`] registry, see
+/// [`ChainRegistry::fingerprint`], as if it had no chains if it failed to load.
+pub fn registry_fingerprint() -> [u8; 32] {
+    ChainRegistry::global().map_or_else(|_| sha256(&[]), ChainRegistry::fingerprint)
+}
+
+/// Compact JSON with sorted object keys.
+pub(crate) fn canonical_json(chain: &Chain) -> Vec<u8> {
+    let value = serde_json::to_value(chain).expect("Chain should serialize to JSON value");
+
+    let mut json = Vec::new();
+    write_canonical(&value, &mut json);
+    json
+}
+
+/// Sorts the keys explicitly, `serde_json::Map` keeps the insertion order
+/// when any crate in the build enables the `preserve_order` feature.
+fn write_canonical(value: &Value, json: &mut Vec<u8>) {
+    match value {
+        Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by_key(|&(key, _)| key);
+
+            json.push(b'{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    json.push(b',');
+                }
+                serde_json::to_writer(&mut *json, key).expect("JSON string should serialize");
+                json.push(b':');
+                write_canonical(value, json);
+            }
+            json.push(b'}');
+        }
+        Value::Array(array) => {
+            json.push(b'[');
+            for (index, value) in array.iter().enumerate() {
+                if index > 0 {
+                    json.push(b',');
+                }
+                write_canonical(value, json);
+            }
+            json.push(b']');
+        }
+        scalar => serde_json::to_writer(&mut *json, scalar).expect("JSON value should serialize"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_fingerprints() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert_eq!(ethereum.fingerprint(), ethereum.clone().fingerprint());
+
+        let mut changed = ethereum.clone();
+        changed.rpc.push("https://rpc.example.com".into());
+        assert_ne!(ethereum.fingerprint(), changed.fingerprint());
+
+        let registry = ChainRegistry::try_load().expect("Should load the embedded chains");
+        assert_eq!(registry_fingerprint(), registry.fingerprint());
+        assert_ne!(
+            registry.fingerprint(),
+            registry.retain(|chain| chain.chain_id != 1).fingerprint()
+        );
+    }
+
+    #[test]
+    fn canonical_json_is_sorted() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let json = String::from_utf8(canonical_json(&ethereum)).unwrap();
+
+        assert!(json.find("\"chain\"").unwrap() < json.find("\"name\"").unwrap());
+        assert!(!json.contains('\n'));
+
+        let mut nested = Vec::new();
+        write_canonical(
+            &serde_json::json!({ "b": 1, "a": [{ "d": null, "c": "x\"" }] }),
+            &mut nested,
+        );
+        assert_eq!(
+            r#"{"a":[{"c":"x\"","d":null}],"b":1}"#,
+            String::from_utf8(nested).unwrap()
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! The token fees are paid in, which on custom-gas-token L2s differs from
+//! the [`NativeCurrency`](crate::NativeCurrency) used for display.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error, registry, Chain, ChainRegistry, Error, RegistryBuilder};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasToken {
+    pub symbol: String,
+    pub decimals: u8,
+    /// The `0x` prefixed address of the token on the parent chain, if it's bridged from one
+    #[serde(default)]
+    pub parent_address: Option<String>,
+}
+
+impl RegistryBuilder {
+    /// The gas tokens by chain id of the chains whose fee token differs from
+    /// their native currency, none by default.
+    pub fn gas_tokens(mut self, overlay: HashMap<u64, GasToken>) -> Self {
+        self.overlays.gas_tokens = overlay;
+        self
+    }
+}
+
+impl ChainRegistry {
+    /// The gas token overlay, see [`RegistryBuilder::gas_tokens`].
+    pub fn gas_tokens(&self) -> &HashMap<u64, GasToken> {
+        &self.overlays().gas_tokens
+    }
+}
+
+/// See [`ChainRegistry::gas_tokens`].
+pub fn gas_tokens() -> &'static HashMap<u64, GasToken> {
+    &registry::overlays().gas_tokens
+}
+
+/// Parses a gas token overlay, a JSON object of chain ids to [`GasToken`]s.
+pub fn gas_tokens_from_json(json: &str) -> Result<HashMap<u64, GasToken>, Error> {
+    serde_json::from_str(json).map_err(error::deserialize)
+}
+
+impl Chain {
+    /// The token fees are paid in, the native currency unless the gas token
+    /// overlay of the registry the chain was loaded by says otherwise.
+    pub fn gas_token(&self) -> GasToken {
+        self.overlays
+            .gas_tokens
+            .get(&self.chain_id)
+            .cloned()
+            .unwrap_or_else(|| GasToken {
+                symbol: self.native_currency.symbol.clone(),
+                decimals: u8::try_from(self.native_currency.decimals.max(0)).unwrap_or(u8::MAX),
+                parent_address: None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_gas_token() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert_eq!(
+            GasToken {
+                symbol: "ETH".into(),
+                decimals: 18,
+                parent_address: None,
+            },
+            ethereum.gas_token()
+        );
+    }
+
+    #[test]
+    fn overlay_from_json() {
+        let overlay = gas_tokens_from_json(
+            r#"{ "123": { "symbol": "USDC", "decimals": 6, "parentAddress": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48" } }"#,
+        )
+        .expect("Should deserialize overlay");
+
+        assert_eq!(6, overlay[&123].decimals);
+
+        let usdc = overlay[&123].clone();
+        let registry = ChainRegistry::builder()
+            .gas_tokens(HashMap::from([(1, usdc.clone())]))
+            .load()
+            .expect("Should load the embedded chains");
+        assert_eq!(usdc, registry.get(1).unwrap().gas_token());
+    }
+}
@@ -0,0 +1,163 @@
+//! Exports of the graph of [`Chain::parent`] relationships, e.g. the L2s of
+//! Ethereum Mainnet, for visualization tools.
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::{Chain, ChainRegistry};
+
+/// A `child -> parent` edge of the graph.
+struct Edge<'a> {
+    child: &'a Chain,
+    /// The chain id of the parent, which might not be in the registry
+    parent_id: u64,
+    kind: &'a str,
+    bridges: Vec<&'a str>,
+}
+
+impl ChainRegistry {
+    /// The graph in the [DOT](https://graphviz.org/doc/info/lang.html) language.
+    pub fn export_dot(&self) -> String {
+        let (nodes, edges) = self.graph();
+
+        let mut dot = String::from("digraph chains {\n");
+        for chain_id in nodes {
+            match self.get(chain_id) {
+                Some(chain) => writeln!(
+                    dot,
+                    "  {} [label={} short_name={} symbol={}];",
+                    chain_id,
+                    dot_string(chain.display_name()),
+                    dot_string(&chain.short_name),
+                    dot_string(&chain.native_currency.symbol)
+                ),
+                None => writeln!(dot, "  {};", chain_id),
+            }
+            .unwrap();
+        }
+        for edge in edges {
+            writeln!(
+                dot,
+                "  {} -> {} [label={} bridges={}];",
+                edge.child.chain_id,
+                edge.parent_id,
+                dot_string(edge.kind),
+                dot_string(&edge.bridges.join(" "))
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// The graph in the [GraphML](http://graphml.graphdrawing.org/) format.
+    pub fn export_graphml(&self) -> String {
+        let (nodes, edges) = self.graph();
+
+        let mut graphml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n",
+            "  <key id=\"short_name\" for=\"node\" attr.name=\"short_name\" attr.type=\"string\"/>\n",
+            "  <key id=\"symbol\" for=\"node\" attr.name=\"symbol\" attr.type=\"string\"/>\n",
+            "  <key id=\"type\" for=\"edge\" attr.name=\"type\" attr.type=\"string\"/>\n",
+            "  <key id=\"bridges\" for=\"edge\" attr.name=\"bridges\" attr.type=\"string\"/>\n",
+            "  <graph id=\"chains\" edgedefault=\"directed\">\n",
+        ));
+        for chain_id in nodes {
+            match self.get(chain_id) {
+                Some(chain) => writeln!(
+                    graphml,
+                    "    <node id=\"{}\"><data key=\"name\">{}</data><data key=\"short_name\">{}</data><data key=\"symbol\">{}</data></node>",
+                    chain_id,
+                    xml_escape(chain.display_name()),
+                    xml_escape(&chain.short_name),
+                    xml_escape(&chain.native_currency.symbol)
+                ),
+                None => writeln!(graphml, "    <node id=\"{}\"/>", chain_id),
+            }
+            .unwrap();
+        }
+        for edge in edges {
+            writeln!(
+                graphml,
+                "    <edge source=\"{}\" target=\"{}\"><data key=\"type\">{}</data><data key=\"bridges\">{}</data></edge>",
+                edge.child.chain_id,
+                edge.parent_id,
+                xml_escape(edge.kind),
+                xml_escape(&edge.bridges.join(" "))
+            )
+            .unwrap();
+        }
+        graphml.push_str("  </graph>\n</graphml>\n");
+
+        graphml
+    }
+
+    /// The chain ids of all the children and parents, and the edges between
+    /// them, both in ascending chain id order.
+    fn graph(&self) -> (BTreeSet<u64>, Vec<Edge<'_>>) {
+        let edges = self
+            .iter()
+            .filter_map(|child| {
+                let parent = child.parent.as_ref()?;
+
+                Some(Edge {
+                    child,
+                    parent_id: parent.chain_id()?,
+                    kind: &parent.kind,
+                    bridges: parent
+                        .bridges
+                        .iter()
+                        .map(|bridge| bridge.url.as_str())
+                        .collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let nodes = edges
+            .iter()
+            .flat_map(|edge| [edge.child.chain_id, edge.parent_id])
+            .collect();
+
+        (nodes, edges)
+    }
+}
+
+fn dot_string(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn xml_escape(string: &str) -> String {
+    string
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_graph() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        let children = registry
+            .iter()
+            .filter(|chain| chain.parent.as_ref().and_then(|p| p.chain_id()).is_some())
+            .count();
+
+        let dot = registry.export_dot();
+        assert!(dot.starts_with("digraph chains {\n"));
+        assert_eq!(children, dot.matches(" -> ").count());
+
+        let graphml = registry.export_graphml();
+        assert_eq!(children, graphml.matches("<edge ").count());
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(r#""a \"b\" \\""#, dot_string(r#"a "b" \"#));
+        assert_eq!("&lt;a&gt; &amp; &quot;b&quot;", xml_escape(r#"<a> & "b""#));
+    }
+}
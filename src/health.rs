@@ -0,0 +1,92 @@
+//! Health checks of user configured chain ids against the [`ChainRegistry`].
+use std::collections::BTreeSet;
+
+use crate::{Chain, ChainRegistry};
+
+/// The configured chain ids which need attention, each sorted and deduplicated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigHealth {
+    /// Not in the registry
+    pub unknown: Vec<u64>,
+    /// [`Chain::is_deprecated`](crate::Chain::is_deprecated)
+    pub deprecated: Vec<u64>,
+    /// [`Chain::is_flagged`](crate::Chain::is_flagged)
+    pub flagged: Vec<u64>,
+}
+
+impl ConfigHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.unknown.is_empty() && self.deprecated.is_empty() && self.flagged.is_empty()
+    }
+}
+
+impl ChainRegistry {
+    /// The known chains out of `chain_ids`, in ascending chain id order.
+    pub fn intersect(&self, chain_ids: impl IntoIterator<Item = u64>) -> Vec<&Chain> {
+        sorted(chain_ids)
+            .into_iter()
+            .filter_map(|chain_id| self.get(chain_id))
+            .collect()
+    }
+
+    /// The unknown chain ids out of `chain_ids`, sorted.
+    pub fn difference(&self, chain_ids: impl IntoIterator<Item = u64>) -> Vec<u64> {
+        sorted(chain_ids)
+            .into_iter()
+            .filter(|&chain_id| self.get(chain_id).is_none())
+            .collect()
+    }
+
+    /// Checks which of the `chain_ids` are unknown, deprecated or flagged.
+    pub fn health(&self, chain_ids: impl IntoIterator<Item = u64>) -> ConfigHealth {
+        let mut health = ConfigHealth::default();
+
+        for chain_id in sorted(chain_ids) {
+            let Some(chain) = self.get(chain_id) else {
+                health.unknown.push(chain_id);
+                continue;
+            };
+            if chain.is_deprecated() {
+                health.deprecated.push(chain_id);
+            }
+            if chain.is_flagged() {
+                health.flagged.push(chain_id);
+            }
+        }
+
+        health
+    }
+}
+
+fn sorted(chain_ids: impl IntoIterator<Item = u64>) -> BTreeSet<u64> {
+    chain_ids.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersect_and_difference() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        let chain_ids = [137, u64::MAX, 1, 1];
+
+        assert_eq!(
+            vec![1, 137],
+            registry
+                .intersect(chain_ids)
+                .iter()
+                .map(|chain| chain.chain_id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![u64::MAX], registry.difference(chain_ids));
+    }
+
+    #[test]
+    fn config_health() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+
+        assert!(registry.health([1, 56, 137]).is_healthy());
+        assert_eq!(vec![u64::MAX], registry.health([1, u64::MAX]).unknown);
+    }
+}
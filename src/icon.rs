@@ -0,0 +1,67 @@
+//! The metadata of the icons referenced by [`Chain::icon`] and [`Explorer::icon`].
+use serde::{Deserialize, Serialize};
+
+use crate::{embedded, error, Chain, Error, Explorer};
+
+/// An entry of an `_data/icons/NAME.json` icon file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Icon {
+    /// E.g. `ipfs://QmdwQDr6vmBtXmK2TmknkEuZNoaDqTasFdZdu3DRw8b2wt`
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+    /// E.g. `png` or `svg`
+    pub format: String,
+}
+
+/// The variants of the icon with the name, embedded at build time.
+pub fn icon_metadata(name: &str) -> Result<Vec<Icon>, Error> {
+    let json = embedded::icon_file(name).ok_or_else(|| error::icon_not_found(name))?;
+
+    serde_json::from_str(json).map_err(error::deserialize)
+}
+
+impl Chain {
+    /// The metadata of the [`Chain::icon`], empty if the chain has no icon.
+    pub fn icon_metadata(&self) -> Result<Vec<Icon>, Error> {
+        match &self.icon {
+            Some(name) => icon_metadata(name).map_err(|err| err.with_chain_id(self.chain_id)),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl Explorer {
+    /// The metadata of the [`Explorer::icon`], empty if the explorer has no icon.
+    pub fn icon_metadata(&self) -> Result<Vec<Icon>, Error> {
+        match &self.icon {
+            Some(name) => icon_metadata(name),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_icons() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let icons = ethereum
+            .icon_metadata()
+            .expect("Ethereum icon file should exist");
+
+        assert!(!icons.is_empty());
+        assert!(icons.iter().all(|icon| icon.url.starts_with("ipfs://")));
+    }
+
+    #[test]
+    fn missing_icon() {
+        let error = icon_metadata("not-an-icon").expect_err("Icon file should not exist");
+
+        assert!(error.is_not_found());
+    }
+}
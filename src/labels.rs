@@ -0,0 +1,230 @@
+//! Kubernetes-style key-value labels on chains and label selectors.
+//!
+//! ```
+//! use evm_chains::{labels::LabelOverlay, ChainRegistry};
+//!
+//! let overlay = LabelOverlay::default()
+//!     .label(1, "tier", "prod")
+//!     .label(137, "tier", "prod")
+//!     .label(137, "stack", "pos");
+//! let registry = ChainRegistry::builder()
+//!     .label_overlay(overlay)
+//!     .load()
+//!     .expect("Should load the chains");
+//!
+//! let chains = registry.select("tier=prod,stack!=pos").expect("Selector should be valid");
+//! assert!(chains.iter().all(|chain| chain.chain_id == 1));
+//! ```
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error, registry, Chain, ChainRegistry, Error, RegistryBuilder};
+
+/// Returned by [`Chain::labels`] for chains without labels.
+static NO_LABELS: BTreeMap<String, String> = BTreeMap::new();
+
+/// Labels by chain id, the chain data has no labels of its own.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LabelOverlay {
+    pub labels: BTreeMap<u64, BTreeMap<String, String>>,
+}
+
+impl LabelOverlay {
+    /// Parses a JSON object of chain ids to label objects, e.g. `{ "1": { "tier": "prod" } }`.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(error::deserialize)
+    }
+
+    pub fn label(
+        mut self,
+        chain_id: u64,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.labels
+            .entry(chain_id)
+            .or_default()
+            .insert(key.into(), value.into());
+        self
+    }
+}
+
+impl RegistryBuilder {
+    /// Labels the registry's chains for [`Chain::labels`] and
+    /// [`ChainRegistry::select`], no chain is labeled by default.
+    pub fn label_overlay(mut self, overlay: LabelOverlay) -> Self {
+        self.overlays.labels = overlay;
+        self
+    }
+}
+
+/// See [`ChainRegistry::label_overlay`].
+pub fn label_overlay() -> &'static LabelOverlay {
+    &registry::overlays().labels
+}
+
+/// A single requirement of a [`Selector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Requirement {
+    /// `key=value` or `key==value`
+    Equals(String, String),
+    /// `key!=value`, also matched by chains without the label
+    NotEquals(String, String),
+    /// `key`
+    Exists(String),
+    /// `!key`
+    NotExists(String),
+}
+
+impl Requirement {
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        match self {
+            Requirement::Equals(key, value) => labels.get(key) == Some(value),
+            Requirement::NotEquals(key, value) => labels.get(key) != Some(value),
+            Requirement::Exists(key) => labels.contains_key(key),
+            Requirement::NotExists(key) => !labels.contains_key(key),
+        }
+    }
+}
+
+/// Comma separated [`Requirement`]s which should all match, e.g. `tier=prod,stack!=opstack`.
+///
+/// An empty selector matches all the chains.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Selector {
+    pub requirements: Vec<Requirement>,
+}
+
+impl Selector {
+    pub fn parse(selector: &str) -> Result<Self, Error> {
+        let requirements = selector
+            .split(',')
+            .map(str::trim)
+            .filter(|requirement| !requirement.is_empty())
+            .map(|requirement| {
+                let requirement = if let Some((key, value)) = requirement.split_once("!=") {
+                    Requirement::NotEquals(key.trim().into(), value.trim().into())
+                } else if let Some((key, value)) = requirement
+                    .split_once("==")
+                    .or_else(|| requirement.split_once('='))
+                {
+                    Requirement::Equals(key.trim().into(), value.trim().into())
+                } else if let Some(key) = requirement.strip_prefix('!') {
+                    Requirement::NotExists(key.trim().into())
+                } else {
+                    Requirement::Exists(requirement.into())
+                };
+
+                match &requirement {
+                    Requirement::Equals(key, _)
+                    | Requirement::NotEquals(key, _)
+                    | Requirement::Exists(key)
+                    | Requirement::NotExists(key)
+                        if is_valid_key(key) =>
+                    {
+                        Ok(requirement)
+                    }
+                    _ => Err(error::selector(selector)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { requirements })
+    }
+
+    pub fn matches(&self, chain: &Chain) -> bool {
+        let labels = chain.labels();
+
+        self.requirements
+            .iter()
+            .all(|requirement| requirement.matches(labels))
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+}
+
+impl Chain {
+    /// The labels of the chain in the label overlay of the registry the chain
+    /// was loaded by.
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        self.overlays
+            .labels
+            .labels
+            .get(&self.chain_id)
+            .unwrap_or(&NO_LABELS)
+    }
+}
+
+impl ChainRegistry {
+    /// The labels of the chains, see [`RegistryBuilder::label_overlay`].
+    pub fn label_overlay(&self) -> &LabelOverlay {
+        &self.overlays().labels
+    }
+
+    /// The chains matching the [`Selector`], in ascending chain id order.
+    pub fn select(&self, selector: &str) -> Result<Vec<&Chain>, Error> {
+        let selector = Selector::parse(selector)?;
+
+        Ok(self
+            .iter()
+            .filter(|chain| selector.matches(chain))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_selector() {
+        assert_eq!(
+            vec![
+                Requirement::Equals("tier".into(), "prod".into()),
+                Requirement::NotEquals("stack".into(), "opstack".into()),
+                Requirement::Exists("team".into()),
+                Requirement::NotExists("legacy".into()),
+            ],
+            Selector::parse("tier=prod, stack!=opstack,team,!legacy")
+                .expect("Selector should be valid")
+                .requirements
+        );
+        assert!(Selector::parse("=prod").is_err());
+        assert!(Selector::parse("").unwrap().requirements.is_empty());
+    }
+
+    #[test]
+    fn match_labels() {
+        let labels = BTreeMap::from([("tier".to_string(), "prod".to_string())]);
+
+        assert!(Requirement::Equals("tier".into(), "prod".into()).matches(&labels));
+        assert!(Requirement::NotEquals("stack".into(), "opstack".into()).matches(&labels));
+        assert!(!Requirement::NotExists("tier".into()).matches(&labels));
+    }
+
+    #[test]
+    fn select() {
+        let registry = ChainRegistry::builder()
+            .label_overlay(LabelOverlay::default().label(56, "tier", "prod"))
+            .load()
+            .expect("Should load the embedded chains");
+
+        assert_eq!(
+            vec![56],
+            registry
+                .select("tier=prod")
+                .expect("Selector should be valid")
+                .iter()
+                .map(|chain| chain.chain_id)
+                .collect::<Vec<_>>()
+        );
+        assert!(Chain::get(56).unwrap().labels().is_empty());
+    }
+}
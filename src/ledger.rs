@@ -0,0 +1,174 @@
+//! Network descriptors in the TLV format used by Ledger-style dynamic network
+//! support.
+//!
+//! The payload is a sequence of `tag | length | value` records, lengths use
+//! the DER encoding. An optional [`Signer`] signs all preceding records and
+//! its signature is appended as the last [`tag::DER_SIGNATURE`] record.
+use std::error::Error as StdError;
+
+use crate::{error, Chain, Error};
+
+/// The record tags of a network descriptor.
+pub mod tag {
+    pub const STRUCTURE_TYPE: u8 = 0x01;
+    pub const STRUCTURE_VERSION: u8 = 0x02;
+    pub const DER_SIGNATURE: u8 = 0x15;
+    pub const CHAIN_ID: u8 = 0x23;
+    pub const TICKER: u8 = 0x24;
+    pub const BLOCKCHAIN_FAMILY: u8 = 0x51;
+    pub const NETWORK_NAME: u8 = 0x52;
+}
+
+/// [`tag::STRUCTURE_TYPE`] value of a dynamic network descriptor
+pub const STRUCTURE_TYPE_DYNAMIC_NETWORK: u8 = 0x08;
+/// [`tag::STRUCTURE_VERSION`] value produced by this module
+pub const STRUCTURE_VERSION: u8 = 0x01;
+/// [`tag::BLOCKCHAIN_FAMILY`] value for EVM chains
+pub const BLOCKCHAIN_FAMILY_ETHEREUM: u8 = 0x01;
+
+/// Signs the descriptor payload, e.g. with an HSM-backed key.
+pub trait Signer {
+    type Error;
+
+    /// Returns the DER encoded signature of the payload.
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, Self::Error>;
+}
+
+impl Chain {
+    /// The unsigned network descriptor of this chain.
+    ///
+    /// Fails if a record value, e.g. the name, is 64KiB or longer.
+    pub fn to_ledger_descriptor(&self) -> Result<Vec<u8>, Error> {
+        self.ledger_records()
+            .map_err(|reason| error::descriptor(self.chain_id, reason))
+    }
+
+    fn ledger_records(&self) -> Result<Vec<u8>, String> {
+        let mut payload = Vec::new();
+
+        push_record(
+            &mut payload,
+            tag::STRUCTURE_TYPE,
+            &[STRUCTURE_TYPE_DYNAMIC_NETWORK],
+        )?;
+        push_record(&mut payload, tag::STRUCTURE_VERSION, &[STRUCTURE_VERSION])?;
+        push_record(
+            &mut payload,
+            tag::BLOCKCHAIN_FAMILY,
+            &[BLOCKCHAIN_FAMILY_ETHEREUM],
+        )?;
+        push_record(&mut payload, tag::CHAIN_ID, &self.chain_id.to_be_bytes())?;
+        push_record(
+            &mut payload,
+            tag::NETWORK_NAME,
+            self.sanitized_name().as_bytes(),
+        )?;
+        push_record(
+            &mut payload,
+            tag::TICKER,
+            self.native_currency.symbol.as_bytes(),
+        )?;
+
+        Ok(payload)
+    }
+
+    /// The network descriptor of this chain with the signature appended.
+    ///
+    /// Fails with the error of the signer as the source.
+    pub fn to_signed_ledger_descriptor<S>(&self, signer: &S) -> Result<Vec<u8>, Error>
+    where
+        S: Signer,
+        S::Error: StdError + Send + Sync + 'static,
+    {
+        let mut payload = self.to_ledger_descriptor()?;
+        let signature = signer
+            .sign(&payload)
+            .map_err(|err| error::descriptor(self.chain_id, err))?;
+        push_record(&mut payload, tag::DER_SIGNATURE, &signature)
+            .map_err(|reason| error::descriptor(self.chain_id, reason))?;
+
+        Ok(payload)
+    }
+}
+
+/// Fails with the reason if the value doesn't fit the 2 byte DER length.
+fn push_record(payload: &mut Vec<u8>, tag: u8, value: &[u8]) -> Result<(), String> {
+    let len = value.len();
+    let mut header = vec![tag];
+    match len {
+        0..=0x7F => header.push(len as u8),
+        0x80..=0xFF => header.extend([0x81, len as u8]),
+        _ => {
+            let len = u16::try_from(len)
+                .map_err(|_| format!("record {:#04x} of {} bytes is 64KiB or longer", tag, len))?;
+            header.push(0x82);
+            header.extend(len.to_be_bytes());
+        }
+    }
+
+    payload.extend(header);
+    payload.extend_from_slice(value);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedSigner;
+
+    impl Signer for FixedSigner {
+        type Error = std::convert::Infallible;
+
+        fn sign(&self, _payload: &[u8]) -> Result<Vec<u8>, Self::Error> {
+            Ok(vec![0x30, 0x02, 0x00, 0x00])
+        }
+    }
+
+    #[test]
+    fn ledger_descriptor() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        let mut expected = vec![0x01, 1, 0x08, 0x02, 1, 0x01, 0x51, 1, 0x01];
+        expected.extend([0x23, 8, 0, 0, 0, 0, 0, 0, 0, 1]);
+        expected.extend([0x52, 16]);
+        expected.extend(b"Ethereum Mainnet");
+        expected.extend([0x24, 3]);
+        expected.extend(b"ETH");
+        assert_eq!(expected, ethereum.to_ledger_descriptor().unwrap());
+
+        expected.extend([0x15, 4, 0x30, 0x02, 0x00, 0x00]);
+        assert_eq!(
+            expected,
+            ethereum.to_signed_ledger_descriptor(&FixedSigner).unwrap()
+        );
+    }
+
+    #[test]
+    fn oversized_records() {
+        let mut chain = Chain::get(1).expect("Chain(1) should exist");
+        chain.native_currency.symbol = "E".repeat(0x1_0000);
+
+        let error = chain
+            .to_ledger_descriptor()
+            .expect_err("The ticker is 64KiB long");
+        assert_eq!(crate::error::Kind::Descriptor, error.kind());
+        assert_eq!(Some(1), error.chain_id());
+    }
+
+    #[test]
+    fn der_lengths() {
+        let mut payload = Vec::new();
+        push_record(&mut payload, 0x52, &[0; 200]).unwrap();
+        assert_eq!([0x52, 0x81, 200], payload[..3]);
+
+        payload.clear();
+        push_record(&mut payload, 0x52, &[0; 300]).unwrap();
+        assert_eq!([0x52, 0x82, 0x01, 0x2C], payload[..4]);
+
+        payload.clear();
+        assert!(push_record(&mut payload, 0x52, &[0; 0x1_0000]).is_err());
+        assert!(payload.is_empty());
+    }
+}
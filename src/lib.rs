@@ -1,67 +1,171 @@
 //! Crate containing the list of Ethereum Virtual Machine compatible chains.
 //!
-//! The crate loads the available chains list from the
-//! [`ethereum-lists/chains`][ethereum-list-chains] as a `git` submodule.
+//! The crate embeds the available chains list from the
+//! [`ethereum-lists/chains`][ethereum-list-chains] `git` submodule at build
+//! time, so no filesystem access is needed at runtime.
+//!
+//! # Features
+//!
+//! - `full` (default) - the `Chain` struct and all the APIs built on top of it.
+//! - `minimal` - `chain_name(id)`, `chain_symbol(id)` and `chain_decimals(id)`
+//!   `const fn`s over tables generated at build time, without `serde`.
+//! - `mini` - `ChainMini` with only the `chains_mini.json` fields (name, chain
+//!   id, short name, rpc & native currency), without `serde`.
+//!
+//! # Build-time filtering
+//!
+//! Setting the `EVM_CHAINS_FILTER` environment variable at build time, e.g.
+//! `EVM_CHAINS_FILTER="mainnet && !flagged"`, includes only the matching
+//! chains. See `build/filter.rs` for the supported expressions.
 //!
 //! [ethereum-list-chains]: https://github.com/ethereum-lists/chains
-use std::{collections::HashMap, fmt::Debug, fs::File, io::BufReader};
+#[cfg(feature = "full")]
+use std::{borrow::Cow, fmt::Debug, fs::File, io::BufReader};
 
+#[cfg(feature = "full")]
 pub use error::Error;
-
+#[cfg(feature = "minimal")]
+pub use minimal::{chain_decimals, chain_name, chain_symbol};
+#[cfg(feature = "full")]
+pub use model::{check_compat, model_version};
+#[cfg(feature = "full")]
+pub use registry::{ChainRegistry, RegistryBuilder};
+pub use tables::ChainIdOf;
+
+#[cfg(feature = "full")]
+pub mod blocklist;
+#[cfg(feature = "full")]
+pub mod brand;
+#[cfg(feature = "full")]
+pub mod bulk;
+#[cfg(feature = "full")]
+pub mod canary;
+#[cfg(feature = "full")]
+pub mod capabilities;
+#[cfg(feature = "full")]
+pub mod compact;
+#[cfg(feature = "full")]
+pub mod doctor;
+#[cfg(feature = "full")]
+pub mod egress;
+#[cfg(feature = "full")]
+pub mod explorer;
+#[cfg(feature = "full")]
+pub mod ext;
+#[cfg(feature = "full")]
+pub mod fingerprint;
+#[cfg(feature = "full")]
+pub mod gas;
+#[cfg(feature = "full")]
+pub mod graph;
+#[cfg(feature = "full")]
+pub mod health;
+#[cfg(feature = "full")]
+pub mod icon;
+#[cfg(feature = "full")]
+pub mod labels;
+#[cfg(feature = "full")]
+pub mod ledger;
+#[cfg(feature = "full")]
+pub mod lint;
+#[cfg(feature = "full")]
+pub mod logging;
+#[cfg(feature = "full")]
+pub mod merge;
+#[cfg(feature = "full")]
+pub mod merkle;
+#[cfg(feature = "full")]
+pub mod metrics;
+#[cfg(feature = "mini")]
+pub mod mini;
+#[cfg(feature = "full")]
+pub mod model;
+#[cfg(feature = "full")]
+pub mod network;
+#[cfg(feature = "full")]
+pub mod pin;
+#[cfg(feature = "full")]
+pub mod policy;
+#[cfg(feature = "full")]
+pub mod query;
+#[cfg(feature = "full")]
+pub mod registry;
+#[cfg(feature = "full")]
+pub mod replay;
+#[cfg(feature = "full")]
+pub mod resolve;
+#[cfg(feature = "full")]
+pub mod rpc;
+#[cfg(feature = "full")]
+pub mod sandbox;
+pub mod sanitize;
+#[cfg(feature = "full")]
+pub mod signing;
+#[cfg(feature = "full")]
+pub mod sourcify;
+#[cfg(feature = "full")]
+pub mod status;
+#[cfg(feature = "full")]
+pub mod symbols;
+pub mod tables;
+#[cfg(feature = "full")]
+pub mod tags;
+#[cfg(feature = "full")]
+pub mod template;
+#[cfg(feature = "full")]
+pub mod transaction;
+#[cfg(feature = "full")]
+pub mod upstream;
+#[cfg(feature = "full")]
+pub mod view;
+
+#[cfg(feature = "full")]
+mod de;
+#[cfg(feature = "full")]
+mod embedded;
+#[cfg(feature = "full")]
+mod sha256;
+#[cfg(feature = "full")]
+mod testnet;
+#[cfg(feature = "full")]
+mod url;
+
+#[cfg(feature = "minimal")]
+mod minimal;
+
+#[cfg(feature = "full")]
 use serde::{Deserialize, Serialize};
 
-use once_cell::sync::Lazy;
-static CHAINS: Lazy<HashMap<u64, Chain>> = Lazy::new(|| {
-    let mut chains = HashMap::new();
-
-    let chain_files = std::fs::read_dir("ethereum-list/chains/_data/chains/")
-        .expect("Directory should be readable");
+/// Reads the chain file embedded at build time.
+#[cfg(feature = "full")]
+fn read_chain_file(chain_id: u64) -> Result<serde_json::Value, Error> {
+    let json = embedded::chain_file(chain_id).ok_or_else(|| error::not_found(vec![chain_id]))?;
 
-    for entry_result in chain_files {
-        let dir_entry =
-            entry_result.expect("Failed to read directory entry from chains data directory");
-
-        let file_type = dir_entry
-            .file_type()
-            .expect("Failed to get the type of file entry in the chains data directory");
-
-        let file_name = dir_entry
-            .file_name()
-            .into_string()
-            .expect("Chain file name should contain valid Unicode string");
+    serde_json::from_str(json).map_err(|err| error::deserialize(err).with_chain_id(chain_id))
+}
 
-        // handle only files
-        if !file_type.is_file() {
-            continue;
-        }
-        // Strip the prefix `eip155-` & suffix `.json` of the file name
-        let chain_id = file_name
-            .strip_prefix("eip155-")
-            .and_then(|file_name| file_name.strip_suffix(".json"))
-            .expect("Chain file name was in incorrect form, expected: eip155-CHAIN_ID.json")
-            .parse::<u64>()
-            .expect("Chain id in file name should be a valid `u64`");
+/// Reads the chain file from the `ethereum-list/chains` submodule, relative to
+/// the current directory.
+#[cfg(feature = "full")]
+fn read_chain_file_from_disk(chain_id: u64) -> Result<serde_json::Value, Error> {
+    let file_path = format!("ethereum-list/chains/_data/chains/eip155-{}.json", chain_id);
 
-        let chain = Chain::from_file(chain_id).unwrap_or_else(|err| {
-            panic!(
-                "Failed to read/deserialize chain file {}: {}",
-                &file_name, err
-            )
-        });
-        // if there is a chain with this ID already - panic!
-        if chains.insert(chain_id, chain).is_some() {
-            panic!("Duplicate Chain id ({})", chain_id)
-        }
-    }
+    let file =
+        File::open(file_path).map_err(|err| error::open_file(err).with_chain_id(chain_id))?;
+    let reader = BufReader::new(file);
 
-    chains
-});
+    serde_json::from_reader(reader).map_err(|err| error::deserialize(err).with_chain_id(chain_id))
+}
 
+#[cfg(feature = "full")]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Chain {
     /// E.g. "Ethereum Mainnet"
     pub name: String,
+    /// A more descriptive name than [`Chain::name`], e.g. "Ethereum Testnet Goerli"
+    pub title: Option<String>,
     /// E.g. "ETH"
     pub chain: String,
     /// E.g. "mainnet"
@@ -76,69 +180,428 @@ pub struct Chain {
     #[serde(rename = "infoURL")]
     pub info_url: String,
     pub short_name: String,
+    #[serde(deserialize_with = "de::u64")]
     pub chain_id: u64,
+    #[serde(deserialize_with = "de::u64")]
     pub network_id: u64,
+    #[serde(default, deserialize_with = "de::option_u64")]
     pub slip44: Option<u64>,
     pub ens: Option<Ens>,
     #[serde(default)]
     pub explorers: Vec<Explorer>,
+    /// E.g. `EIP155`, `EIP1559`
+    #[serde(default)]
+    pub features: Vec<Feature>,
+    /// The chain this one settles on or is bridged to, e.g. Ethereum Mainnet for an L2
+    pub parent: Option<Parent>,
+    #[serde(default)]
+    pub status: status::ChainStatus,
+    /// Warnings about the chain, e.g. `reusedChainId`
+    #[serde(default)]
+    pub red_flags: Vec<String>,
+    /// Attached by the [`ext::Enricher`]s of the registry
+    #[serde(skip)]
+    ext: ext::ChainExtensions,
+    /// The overlays of the registry the chain was loaded by
+    #[serde(skip)]
+    overlays: registry::SharedOverlays,
 }
 
+#[cfg(feature = "full")]
 impl Chain {
+    /// A chain with only the required fields, e.g. a custom chain for a
+    /// [`sandbox::Sandbox`]. The other fields are empty, except
+    /// [`Chain::network_id`] which is the chain id, and can be assigned.
+    ///
+    /// ```
+    /// use evm_chains::{Chain, NativeCurrency};
+    ///
+    /// let mut devnet = Chain::new(31337, "Devnet", "dev", NativeCurrency::new("Ether", "ETH", 18));
+    /// devnet.rpc.push("http://localhost:8545".into());
+    /// ```
+    pub fn new(
+        chain_id: u64,
+        name: impl Into<String>,
+        short_name: impl Into<String>,
+        native_currency: NativeCurrency,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            title: None,
+            chain: String::new(),
+            network: String::new(),
+            icon: None,
+            rpc: Vec::new(),
+            faucets: Vec::new(),
+            native_currency,
+            info_url: String::new(),
+            short_name: short_name.into(),
+            chain_id,
+            network_id: chain_id,
+            slip44: None,
+            ens: None,
+            explorers: Vec::new(),
+            features: Vec::new(),
+            parent: None,
+            status: status::ChainStatus::default(),
+            red_flags: Vec::new(),
+            ext: ext::ChainExtensions::default(),
+            overlays: registry::SharedOverlays::default(),
+        }
+    }
+
+    /// Reads the chain file from the `ethereum-list/chains` submodule, relative
+    /// to the current directory, e.g. to pick up local changes to the data.
+    ///
+    /// [`Chain::get`] uses the chain files embedded at build time instead.
     pub fn from_file(chain_id: u64) -> Result<Self, Error> {
-        let file_path = format!("ethereum-list/chains/_data/chains/eip155-{}.json", chain_id);
+        read_chain_file_from_disk(chain_id).and_then(|value| {
+            serde_json::from_value(value)
+                .map_err(|err| error::deserialize(err).with_chain_id(chain_id))
+        })
+    }
+
+    /// The field at the [JSON pointer][json-pointer] in the serialized
+    /// chain, e.g. `"/nativeCurrency/symbol"` or `"/redFlags/0"`.
+    ///
+    /// The fields `Chain` doesn't model are read from the raw chain file
+    /// embedded at build time, if there's one for the chain id.
+    ///
+    /// [json-pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    pub fn get_field(&self, json_pointer: &str) -> Option<serde_json::Value> {
+        let mut chain = serde_json::to_value(self).ok()?;
+        let field = json_pointer
+            .strip_prefix('/')?
+            .split('/')
+            .next()
+            .map(|field| field.replace("~1", "/").replace("~0", "~"))?;
+
+        match chain.get(&field) {
+            Some(_) => chain.pointer_mut(json_pointer).map(serde_json::Value::take),
+            None => read_chain_file(self.chain_id)
+                .ok()?
+                .pointer_mut(json_pointer)
+                .map(serde_json::Value::take),
+        }
+    }
 
-        let file = File::open(file_path).map_err(error::open_file)?;
-        let reader = BufReader::new(file);
+    /// Whether the chain has [`Chain::red_flags`], wallets should warn users
+    /// before adding it.
+    pub fn is_flagged(&self) -> bool {
+        !self.red_flags.is_empty()
+    }
+
+    pub fn has_feature(&self, feature: &Feature) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// Whether the chain has the EIP-1559 fee market, i.e. transactions
+    /// should set `maxFeePerGas` & `maxPriorityFeePerGas` instead of `gasPrice`.
+    pub fn supports_eip1559(&self) -> bool {
+        self.has_feature(&Feature::Eip1559)
+    }
+
+    /// The [`Chain::parent`] chain, if it's an `eip155` chain which exists.
+    pub fn parent_chain(&self) -> Option<&'static Chain> {
+        let parent_id = self.parent.as_ref()?.chain_id()?;
 
-        serde_json::from_reader(reader).map_err(error::deserialize)
+        registry::chains().get(&parent_id)
     }
 
+    /// The chain from the [`ChainRegistry::global`] registry, `None` if it
+    /// failed to load.
     pub fn get(chain_id: u64) -> Option<Self> {
-        CHAINS.get(&chain_id).cloned()
+        registry::chains().get(&chain_id).cloned()
+    }
+
+    /// All the chains in ascending chain id order.
+    ///
+    /// The order is guaranteed, so exports and pagination built on top of
+    /// it are deterministic.
+    pub fn iter() -> impl DoubleEndedIterator<Item = &'static Chain> + ExactSizeIterator {
+        registry::chains().values()
+    }
+
+    /// The [`Chain::title`] if there is one, otherwise the [`Chain::name`].
+    pub fn display_name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+
+    /// The [`Chain::name`] stripped of control characters, bidi overrides and
+    /// suspicious homoglyphs, see [`sanitize::sanitize`].
+    pub fn sanitized_name(&self) -> Cow<'_, str> {
+        sanitize::sanitize(&self.name)
+    }
+
+    /// The [`Chain::short_name`] sanitized like [`Chain::sanitized_name`].
+    pub fn sanitized_short_name(&self) -> Cow<'_, str> {
+        sanitize::sanitize(&self.short_name)
     }
 }
 
+#[cfg(feature = "full")]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct NativeCurrency {
     pub name: String,
     pub symbol: String,
+    #[serde(deserialize_with = "de::i64")]
     pub decimals: i64,
 }
 
+#[cfg(feature = "full")]
+impl NativeCurrency {
+    pub fn new(name: impl Into<String>, symbol: impl Into<String>, decimals: i64) -> Self {
+        Self {
+            name: name.into(),
+            symbol: symbol.into(),
+            decimals,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Ens {
     /// `0x` prefixed and checksummed address
     pub registry: String,
 }
 
+#[cfg(feature = "full")]
+impl Ens {
+    pub fn new(registry: impl Into<String>) -> Self {
+        Self {
+            registry: registry.into(),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Explorer {
     pub name: String,
     pub url: String,
-    pub standard: String,
+    pub standard: ExplorerStandard,
+    /// The icon file from `ethereum-list/chains`, e.g. `blockscout`
+    pub icon: Option<String>,
 }
 
-pub mod error {
-    use std::{error::Error as StdError, fmt, fmt::Debug, io};
+/// The standard an [`Explorer`] follows, (de)serialized as its name, e.g. `"EIP3091"`.
+#[cfg(feature = "full")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum ExplorerStandard {
+    /// The [EIP-3091](https://eips.ethereum.org/EIPS/eip-3091) `/tx`,
+    /// `/address`, `/block` and `/token` paths
+    Eip3091,
+    /// No standard, `"none"` in the chain files
+    None,
+    /// A standard without a variant, with its name as in the chain file
+    Other(String),
+}
+
+#[cfg(feature = "full")]
+impl Explorer {
+    /// An explorer without an icon.
+    pub fn new(
+        name: impl Into<String>,
+        url: impl Into<String>,
+        standard: ExplorerStandard,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            standard,
+            icon: None,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl ExplorerStandard {
+    /// Parses the name, ignoring case, e.g. `EIP3091`.
+    pub fn from_name(name: &str) -> Self {
+        if name.eq_ignore_ascii_case("EIP3091") {
+            ExplorerStandard::Eip3091
+        } else if name.eq_ignore_ascii_case("none") {
+            ExplorerStandard::None
+        } else {
+            ExplorerStandard::Other(name.into())
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ExplorerStandard::Eip3091 => "EIP3091",
+            ExplorerStandard::None => "none",
+            ExplorerStandard::Other(name) => name,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl From<String> for ExplorerStandard {
+    fn from(name: String) -> Self {
+        match ExplorerStandard::from_name(&name) {
+            ExplorerStandard::Other(_) => ExplorerStandard::Other(name),
+            standard => standard,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl From<ExplorerStandard> for String {
+    fn from(standard: ExplorerStandard) -> Self {
+        match standard {
+            ExplorerStandard::Other(name) => name,
+            standard => standard.name().into(),
+        }
+    }
+}
+
+/// A feature of the chain, (de)serialized as `{ "name": "EIP1559" }`.
+#[cfg(feature = "full")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "FeatureName", into = "FeatureName")]
+#[non_exhaustive]
+pub enum Feature {
+    /// Replay protection with the chain id in the transaction signature
+    Eip155,
+    /// The fee market with a base fee and priority fees
+    Eip1559,
+    /// A feature without a variant, with its name as in the chain file
+    Other(String),
+}
 
-    use thiserror::Error;
+#[cfg(feature = "full")]
+impl Feature {
+    /// Parses the name, ignoring case, e.g. `EIP1559`.
+    pub fn from_name(name: &str) -> Self {
+        if name.eq_ignore_ascii_case("EIP155") {
+            Feature::Eip155
+        } else if name.eq_ignore_ascii_case("EIP1559") {
+            Feature::Eip1559
+        } else {
+            Feature::Other(name.into())
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Feature::Eip155 => "EIP155",
+            Feature::Eip1559 => "EIP1559",
+            Feature::Other(name) => name,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+struct FeatureName {
+    name: String,
+}
+
+#[cfg(feature = "full")]
+impl From<FeatureName> for Feature {
+    fn from(feature: FeatureName) -> Self {
+        Feature::from_name(&feature.name)
+    }
+}
+
+#[cfg(feature = "full")]
+impl From<Feature> for FeatureName {
+    fn from(feature: Feature) -> Self {
+        FeatureName {
+            name: feature.name().into(),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Parent {
+    /// E.g. `L2` or `shard`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// E.g. `eip155-1`
+    pub chain: String,
+    #[serde(default)]
+    pub bridges: Vec<Bridge>,
+}
+
+#[cfg(feature = "full")]
+impl Parent {
+    /// A parent without bridges, e.g. `Parent::new("L2", "eip155-1")`.
+    pub fn new(kind: impl Into<String>, chain: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            chain: chain.into(),
+            bridges: Vec::new(),
+        }
+    }
+
+    /// The chain id of an `eip155-CHAIN_ID` parent chain.
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain.strip_prefix("eip155-")?.parse().ok()
+    }
+}
+
+#[cfg(feature = "full")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Bridge {
+    pub url: String,
+}
+
+#[cfg(feature = "full")]
+impl Bridge {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+#[cfg(feature = "full")]
+pub mod error {
+    use std::{error::Error as StdError, fmt, fmt::Debug, io, sync::Arc};
 
     pub(crate) type BoxError = Box<dyn StdError + Send + Sync>;
 
-    #[derive(Debug, Error)]
-    #[error("{inner}")]
+    /// Cheap to clone, the source is shared, e.g. to return the load error of
+    /// the [`crate::ChainRegistry::global`] registry from every wrapper.
+    #[derive(Debug, Clone)]
     pub struct Error {
         inner: Box<Inner>,
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
     pub(crate) struct Inner {
         kind: Kind,
-        source: Option<BoxError>,
+        chain_id: Option<u64>,
+        source: Option<Arc<dyn StdError + Send + Sync>>,
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.inner)
+        }
+    }
+
+    impl StdError for Error {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            self.inner
+                .source
+                .as_ref()
+                .map(|source| &**source as &(dyn StdError + 'static))
+        }
     }
 
     impl fmt::Display for Inner {
@@ -154,7 +617,17 @@ pub mod error {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
                 Kind::Json => f.write_str("Deserializing json"),
+                Kind::Serialize => f.write_str("Serializing json"),
                 Kind::File => f.write_str("Reading file"),
+                Kind::Pin => f.write_str("Pinned chain invariant"),
+                Kind::ContentPolicy => f.write_str("Content policy"),
+                Kind::ModelVersion => f.write_str("Data model version"),
+                Kind::NotFound => f.write_str("Not found"),
+                Kind::Template => f.write_str("Rendering template"),
+                Kind::Selector => f.write_str("Parsing label selector"),
+                Kind::Transaction => f.write_str("Registry transaction"),
+                Kind::Descriptor => f.write_str("Encoding network descriptor"),
+                Kind::Query => f.write_str("Parsing chain query"),
             }
         }
     }
@@ -163,16 +636,54 @@ pub mod error {
             Self {
                 inner: Box::new(Inner {
                     kind,
-                    source: source.map(Into::into),
+                    chain_id: None,
+                    source: source.map(|source| Arc::from(source.into())),
                 }),
             }
         }
+
+        /// Sets the id of the chain this error is about.
+        pub(crate) fn with_chain_id(mut self, chain_id: u64) -> Self {
+            self.inner.chain_id = Some(chain_id);
+            self
+        }
+
+        pub fn kind(&self) -> Kind {
+            self.inner.kind
+        }
+
+        /// The id of the chain this error is about, if it's known.
+        pub fn chain_id(&self) -> Option<u64> {
+            self.inner.chain_id
+        }
+
+        /// Whether the error was caused by an unknown chain or icon, or a missing chain file.
+        pub fn is_not_found(&self) -> bool {
+            self.inner.kind == Kind::NotFound
+                || self.inner.kind == Kind::File
+                    && self
+                        .inner
+                        .source
+                        .as_ref()
+                        .and_then(|source| source.downcast_ref::<io::Error>())
+                        .is_some_and(|error| error.kind() == io::ErrorKind::NotFound)
+        }
     }
 
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum Kind {
         Json,
+        Serialize,
         File,
+        Pin,
+        ContentPolicy,
+        ModelVersion,
+        NotFound,
+        Template,
+        Selector,
+        Transaction,
+        Descriptor,
+        Query,
     }
 
     pub(crate) fn open_file(error: io::Error) -> Error {
@@ -182,11 +693,113 @@ pub mod error {
     pub(crate) fn deserialize(error: serde_json::Error) -> Error {
         Error::new(Kind::Json, Some(error))
     }
+
+    pub(crate) fn serialize(error: serde_json::Error) -> Error {
+        Error::new(Kind::Serialize, Some(error))
+    }
+
+    pub(crate) fn pin_violation(violation: crate::pin::Violation) -> Error {
+        let chain_id = violation.chain_id();
+
+        Error::new(Kind::Pin, Some(violation)).with_chain_id(chain_id)
+    }
+
+    /// The chain ids which were not found, the first one is set as [`Error::chain_id`].
+    pub(crate) fn not_found(chain_ids: Vec<u64>) -> Error {
+        let error = Error::new(
+            Kind::NotFound,
+            Some(format!("unknown chain ids {:?}", chain_ids)),
+        );
+
+        match chain_ids.first() {
+            Some(&chain_id) => error.with_chain_id(chain_id),
+            None => error,
+        }
+    }
+
+    pub(crate) fn template(reason: impl Into<String>) -> Error {
+        Error::new(Kind::Template, Some(reason.into()))
+    }
+
+    pub(crate) fn selector(selector: &str) -> Error {
+        Error::new(
+            Kind::Selector,
+            Some(format!("invalid selector {:?}", selector)),
+        )
+    }
+
+    pub(crate) fn query(query: &str, reason: impl fmt::Display) -> Error {
+        Error::new(Kind::Query, Some(format!("{} in {:?}", reason, query)))
+    }
+
+    pub(crate) fn transaction(chain_id: u64, reason: impl Into<String>) -> Error {
+        Error::new(Kind::Transaction, Some(reason.into())).with_chain_id(chain_id)
+    }
+
+    pub(crate) fn descriptor<E: Into<BoxError>>(chain_id: u64, source: E) -> Error {
+        Error::new(Kind::Descriptor, Some(source)).with_chain_id(chain_id)
+    }
+
+    pub(crate) fn icon_not_found(name: &str) -> Error {
+        Error::new(Kind::NotFound, Some(format!("unknown icon {:?}", name)))
+    }
+
+    pub(crate) fn model_version(version: u32) -> Error {
+        Error::new(
+            Kind::ModelVersion,
+            Some(format!(
+                "unsupported version {}, expected 1 to {}",
+                version,
+                crate::model::MODEL_VERSION
+            )),
+        )
+    }
+
+    pub(crate) fn content_policy(violation: crate::lint::Lint) -> Error {
+        let chain_id = violation.chain_id;
+
+        Error::new(Kind::ContentPolicy, Some(violation)).with_chain_id(chain_id)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::{error::Error as _, io};
+
+        use super::Kind;
+        use crate::Chain;
+
+        #[test]
+        fn missing_chain_file() {
+            let error = Chain::from_file(u64::MAX).expect_err("Chain file should not exist");
+
+            assert_eq!(Kind::File, error.kind());
+            assert_eq!(Some(u64::MAX), error.chain_id());
+            assert!(error.is_not_found());
+            assert!(error
+                .source()
+                .and_then(|source| source.downcast_ref::<io::Error>())
+                .is_some());
+        }
+
+        #[test]
+        fn invalid_chain_json() {
+            let error = serde_json::from_str::<Chain>("{}")
+                .map_err(super::deserialize)
+                .expect_err("Should fail to deserialize");
+
+            assert_eq!(Kind::Json, error.kind());
+            assert!(!error.is_not_found());
+            assert!(error
+                .source()
+                .and_then(|source| source.downcast_ref::<serde_json::Error>())
+                .is_some());
+        }
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "full"))]
 mod tests {
-    use super::{Chain, CHAINS};
+    use super::{registry::chains, Chain};
 
     static ETHEREUM_FILE: &str = include_str!("../ethereum-list/chains/_data/chains/eip155-1.json");
 
@@ -210,7 +823,7 @@ mod tests {
 
         // first make sure that static is loading all files correctly
         for chain_id in get_chain_ids {
-            let _chain = CHAINS
+            let _chain = chains()
                 .get(&chain_id)
                 .unwrap_or_else(|| panic!("Chain({}) should exist", chain_id));
         }
@@ -220,4 +833,153 @@ mod tests {
                 Chain::get(chain_id).unwrap_or_else(|| panic!("Chain({}) should exist", chain_id));
         }
     }
+
+    #[test]
+    fn get_field() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert_eq!(Some(serde_json::json!(1)), ethereum.get_field("/chainId"));
+        assert_eq!(
+            Some(serde_json::json!(ethereum.native_currency.symbol)),
+            ethereum.get_field("/nativeCurrency/symbol")
+        );
+        assert_eq!(None, ethereum.get_field("/not/a/field"));
+        assert_eq!(None, ethereum.get_field(""));
+    }
+
+    #[test]
+    fn constructed_chain_fields() {
+        let mut devnet = super::Chain::new(
+            31337,
+            "Devnet",
+            "dev",
+            super::NativeCurrency::new("Ether", "ETH", 18),
+        );
+        devnet.rpc.push("http://localhost:8545".into());
+
+        assert_eq!(Some(serde_json::json!("Devnet")), devnet.get_field("/name"));
+        assert_eq!(
+            Some(serde_json::json!("http://localhost:8545")),
+            devnet.get_field("/rpc/0")
+        );
+        assert_eq!(None, devnet.get_field("/rpc/1"));
+
+        // the model is preferred over the embedded chain file
+        devnet.chain_id = 1;
+        assert_eq!(
+            Some(serde_json::json!("dev")),
+            devnet.get_field("/shortName")
+        );
+    }
+
+    #[test]
+    fn constructed_chain() {
+        let mut devnet = super::Chain::new(
+            31337,
+            "Devnet",
+            "dev",
+            super::NativeCurrency::new("Ether", "ETH", 18),
+        );
+        devnet.explorers.push(super::Explorer::new(
+            "local",
+            "http://localhost:4000",
+            super::ExplorerStandard::Eip3091,
+        ));
+        devnet.parent = Some(super::Parent::new("L2", "eip155-1"));
+
+        let json = serde_json::to_string(&devnet).unwrap();
+        assert_eq!(devnet, serde_json::from_str::<super::Chain>(&json).unwrap());
+        assert_eq!(Some(1), devnet.parent.and_then(|parent| parent.chain_id()));
+    }
+
+    #[test]
+    fn explorer_standards() {
+        let standards =
+            serde_json::from_value::<Vec<super::ExplorerStandard>>(serde_json::json!([
+                "EIP3091", "none", "EIP9999"
+            ]))
+            .expect("Should deserialize explorer standards");
+
+        assert_eq!(
+            vec![
+                super::ExplorerStandard::Eip3091,
+                super::ExplorerStandard::None,
+                super::ExplorerStandard::Other("EIP9999".into())
+            ],
+            standards
+        );
+        assert_eq!(
+            serde_json::json!(["EIP3091", "none", "EIP9999"]),
+            serde_json::to_value(&standards).unwrap()
+        );
+    }
+
+    #[test]
+    fn features() {
+        let features = serde_json::from_value::<Vec<super::Feature>>(serde_json::json!([
+            { "name": "EIP155" },
+            { "name": "eip1559" },
+            { "name": "EIP4844" }
+        ]))
+        .expect("Should deserialize features");
+
+        assert_eq!(
+            vec![
+                super::Feature::Eip155,
+                super::Feature::Eip1559,
+                super::Feature::Other("EIP4844".into())
+            ],
+            features
+        );
+        assert_eq!(
+            serde_json::json!({ "name": "EIP1559" }),
+            serde_json::to_value(&features[1]).unwrap()
+        );
+    }
+
+    #[test]
+    fn red_flags() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert!(!ethereum.is_flagged());
+
+        let mut value = serde_json::to_value(&ethereum).unwrap();
+        value["redFlags"] = serde_json::json!(["reusedChainId"]);
+        let flagged = serde_json::from_value::<Chain>(value).expect("Should deserialize");
+        assert!(flagged.is_flagged());
+    }
+
+    #[test]
+    fn display_name() {
+        let mut ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert_eq!(ethereum.name, ethereum.display_name());
+
+        ethereum.title = Some("Ethereum Mainnet (L1)".into());
+        assert_eq!("Ethereum Mainnet (L1)", ethereum.display_name());
+    }
+
+    #[test]
+    fn parent() {
+        let parent = serde_json::from_value::<super::Parent>(serde_json::json!({
+            "type": "L2",
+            "chain": "eip155-1",
+            "bridges": [{ "url": "https://bridge.arbitrum.io" }]
+        }))
+        .expect("Should deserialize parent");
+
+        assert_eq!("L2", parent.kind);
+        assert_eq!(Some(1), parent.chain_id());
+        assert_eq!(1, parent.bridges.len());
+        assert!(Chain::get(1)
+            .expect("Chain(1) should exist")
+            .parent
+            .is_none());
+    }
+
+    #[test]
+    fn iter_in_chain_id_order() {
+        assert_eq!(chains().len(), Chain::iter().len());
+        assert!(Chain::iter()
+            .zip(Chain::iter().skip(1))
+            .all(|(previous, next)| previous.chain_id < next.chain_id));
+    }
 }
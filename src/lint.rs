@@ -0,0 +1,139 @@
+//! Non-fatal findings about the loaded chain files.
+//!
+//! Lints never prevent a chain from loading, they point out upstream data
+//! which the crate had to work around.
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{policy::Rule, Chain};
+
+/// A single finding for a chain file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub chain_id: u64,
+    pub kind: LintKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    /// A numeric field was encoded as a string or a float and was
+    /// deserialized tolerantly.
+    NumberTolerance {
+        /// The JSON field, e.g. `nativeCurrency.decimals`
+        field: &'static str,
+    },
+    /// A display string contained control characters, bidi overrides or
+    /// suspicious homoglyphs, see [`crate::sanitize::sanitize`].
+    Sanitized {
+        /// The JSON field, e.g. `shortName`
+        field: &'static str,
+    },
+    /// A field violates a [`crate::policy::ContentPolicy`] rule.
+    ContentPolicy { field: &'static str, rule: Rule },
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            LintKind::NumberTolerance { field } => write!(
+                f,
+                "Chain({}): `{}` is not encoded as an integer",
+                self.chain_id, field
+            ),
+            LintKind::Sanitized { field } => write!(
+                f,
+                "Chain({}): `{}` had to be sanitized for display",
+                self.chain_id, field
+            ),
+            LintKind::ContentPolicy { field, rule } => {
+                write!(f, "Chain({}): `{}` {}", self.chain_id, field, rule)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Lint {}
+
+/// All the lints found while loading the chains.
+pub fn lints() -> &'static [Lint] {
+    crate::ChainRegistry::global()
+        .map(crate::ChainRegistry::lints)
+        .unwrap_or_default()
+}
+
+/// Lints the raw JSON of a chain file.
+pub(crate) fn lint_value(chain_id: u64, value: &Value) -> Vec<Lint> {
+    const NUMBER_FIELDS: [(&str, &str); 4] = [
+        ("chainId", "/chainId"),
+        ("networkId", "/networkId"),
+        ("slip44", "/slip44"),
+        ("nativeCurrency.decimals", "/nativeCurrency/decimals"),
+    ];
+
+    NUMBER_FIELDS
+        .into_iter()
+        .filter(|(_, pointer)| {
+            value
+                .pointer(pointer)
+                .is_some_and(|number| !number.is_null() && !number.is_u64() && !number.is_i64())
+        })
+        .map(|(field, _)| Lint {
+            chain_id,
+            kind: LintKind::NumberTolerance { field },
+        })
+        .collect()
+}
+
+/// Lints the deserialized chain.
+pub(crate) fn lint_chain(chain: &Chain) -> Vec<Lint> {
+    [
+        ("name", chain.sanitized_name()),
+        ("shortName", chain.sanitized_short_name()),
+    ]
+    .into_iter()
+    .filter(|(_, sanitized)| matches!(sanitized, std::borrow::Cow::Owned(_)))
+    .map(|(field, _)| Lint {
+        chain_id: chain.chain_id,
+        kind: LintKind::Sanitized { field },
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn number_tolerance_lints() {
+        let value = json!({
+            "chainId": "56",
+            "networkId": 56,
+            "slip44": null,
+            "nativeCurrency": { "decimals": 18.0 }
+        });
+
+        assert_eq!(
+            vec![
+                Lint {
+                    chain_id: 56,
+                    kind: LintKind::NumberTolerance { field: "chainId" }
+                },
+                Lint {
+                    chain_id: 56,
+                    kind: LintKind::NumberTolerance {
+                        field: "nativeCurrency.decimals"
+                    }
+                },
+            ],
+            lint_value(56, &value)
+        );
+    }
+
+    #[test]
+    fn loaded_chains_lints() {
+        assert!(lints().iter().all(|lint| lint.chain_id != 1));
+    }
+}
@@ -0,0 +1,97 @@
+//! Compact chain context for log lines and trace spans, instead of the full [`Chain`].
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::Chain;
+
+/// Serializes as `{"chainId":1,"shortName":"eth","family":"ETH"}` for
+/// structured logs and displays as `chain_id=1 short_name=eth family=ETH`
+/// for plain text ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFields<'a> {
+    pub chain_id: u64,
+    pub short_name: &'a str,
+    /// The [`Chain::chain`], e.g. `ETH` for Ethereum and its testnets
+    pub family: &'a str,
+}
+
+impl fmt::Display for LogFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "chain_id={} short_name={} family={}",
+            self.chain_id, self.short_name, self.family
+        )
+    }
+}
+
+/// OpenTelemetry span attribute names, there are no official semantic
+/// conventions for blockchains yet.
+pub mod attribute {
+    /// E.g. `1`
+    pub const CHAIN_ID: &str = "blockchain.chain_id";
+    /// E.g. `Ethereum Mainnet`
+    pub const NETWORK_NAME: &str = "blockchain.network_name";
+    /// E.g. `eth`
+    pub const SHORT_NAME: &str = "blockchain.short_name";
+}
+
+impl Chain {
+    pub fn log_fields(&self) -> LogFields<'_> {
+        LogFields {
+            chain_id: self.chain_id,
+            short_name: &self.short_name,
+            family: &self.chain,
+        }
+    }
+
+    /// The OpenTelemetry span attributes of the chain, see [`attribute`].
+    ///
+    /// The chain id is a decimal string, since OpenTelemetry integers are
+    /// `i64` and chain ids `u64`.
+    pub fn otel_attributes(&self) -> [(&'static str, String); 3] {
+        [
+            (attribute::CHAIN_ID, self.chain_id.to_string()),
+            (attribute::NETWORK_NAME, self.name.clone()),
+            (attribute::SHORT_NAME, self.short_name.clone()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_fields() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let fields = ethereum.log_fields();
+
+        assert_eq!(
+            format!(
+                "chain_id=1 short_name={} family={}",
+                ethereum.short_name, ethereum.chain
+            ),
+            fields.to_string()
+        );
+        assert_eq!(
+            serde_json::json!({
+                "chainId": 1,
+                "shortName": ethereum.short_name,
+                "family": ethereum.chain,
+            }),
+            serde_json::to_value(fields).unwrap()
+        );
+    }
+
+    #[test]
+    fn otel_attributes() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let attributes = ethereum.otel_attributes();
+
+        assert_eq!((attribute::CHAIN_ID, "1".to_string()), attributes[0]);
+        assert_eq!((attribute::NETWORK_NAME, ethereum.name), attributes[1]);
+    }
+}
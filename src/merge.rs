@@ -0,0 +1,240 @@
+//! Merging another registry, e.g. an internal registry of private chains,
+//! into a registry of the public list.
+//!
+//! The chains only in the other registry are added as they are. The fields
+//! of the chains in both are resolved by a [`MergePolicy`] and every
+//! difference is reported as a [`MergeConflict`], so the result can be
+//! audited.
+//!
+//! ```
+//! use evm_chains::{merge::MergePolicy, ChainRegistry};
+//!
+//! let mut public = ChainRegistry::try_load().expect("Should load the chains");
+//! let mut internal = ChainRegistry::try_load().expect("Should load the chains");
+//! let mut ethereum = internal.get(1).expect("Chain(1) should exist").clone();
+//! ethereum.rpc = vec!["https://eth.internal.example".into()];
+//! internal
+//!     .transaction(|tx| {
+//!         tx.replace(ethereum);
+//!         Ok(())
+//!     })
+//!     .expect("Transaction should commit");
+//!
+//! let policy = MergePolicy {
+//!     union_rpcs: true,
+//!     ..MergePolicy::default()
+//! };
+//! let conflicts = public.merge(&internal, policy).expect("Should merge the registries");
+//!
+//! assert_eq!("rpc", conflicts[0].field);
+//! assert!(public.get(1).unwrap().rpc.contains(&"https://eth.internal.example".into()));
+//! ```
+use serde_json::{Map, Value};
+
+use crate::{error, Chain, ChainRegistry, Error};
+
+/// Which of two conflicting fields is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Resolution {
+    /// The field of the registry merged into
+    #[default]
+    PreferUpstream,
+    /// The field of the registry merged in, which is taken as the newer data
+    /// as chains don't record when they were changed
+    PreferNewer,
+}
+
+/// How the fields of the chains in both registries are merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MergePolicy {
+    pub conflicts: Resolution,
+    /// Keep the [`Chain::rpc`] urls of both chains, the upstream ones first,
+    /// instead of resolving them like the other fields
+    pub union_rpcs: bool,
+}
+
+/// A field which differs between the chains of the two registries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub chain_id: u64,
+    /// The serialized field, e.g. `nativeCurrency`
+    pub field: String,
+    pub upstream: Value,
+    pub newer: Value,
+    /// The value in the merged registry
+    pub merged: Value,
+}
+
+impl ChainRegistry {
+    /// Merges the chains of `other` into the registry, returning the
+    /// conflicts in ascending chain id and field name order.
+    ///
+    /// Applied like a [`ChainRegistry::transaction`]: nothing is merged if
+    /// the result is invalid, e.g. a chain of `other` reuses an upstream
+    /// short name, and the merged chains get the overlays of the registry.
+    pub fn merge(
+        &mut self,
+        other: &ChainRegistry,
+        policy: MergePolicy,
+    ) -> Result<Vec<MergeConflict>, Error> {
+        let mut conflicts = Vec::new();
+        let mut inserted = Vec::new();
+        let mut replaced = Vec::new();
+
+        for newer in other.iter() {
+            let Some(upstream) = self.get(newer.chain_id) else {
+                inserted.push(newer.clone());
+                continue;
+            };
+            if upstream == newer {
+                continue;
+            }
+
+            let mut merged = to_object(upstream)?;
+            for (field, newer_value) in to_object(newer)? {
+                let upstream_value = merged.get(&field).cloned().unwrap_or(Value::Null);
+                if upstream_value == newer_value {
+                    continue;
+                }
+
+                let merged_value = match (field.as_str(), policy.conflicts) {
+                    ("rpc", _) if policy.union_rpcs => union(&upstream_value, &newer_value),
+                    (_, Resolution::PreferUpstream) => upstream_value.clone(),
+                    (_, Resolution::PreferNewer) => newer_value.clone(),
+                };
+                merged.insert(field.clone(), merged_value.clone());
+                conflicts.push(MergeConflict {
+                    chain_id: newer.chain_id,
+                    field,
+                    upstream: upstream_value,
+                    newer: newer_value,
+                    merged: merged_value,
+                });
+            }
+
+            replaced.push(
+                serde_json::from_value::<Chain>(Value::Object(merged))
+                    .map_err(|err| error::deserialize(err).with_chain_id(newer.chain_id))?,
+            );
+        }
+
+        self.transaction(|tx| {
+            inserted.into_iter().for_each(|chain| tx.insert(chain));
+            replaced.into_iter().for_each(|chain| tx.replace(chain));
+            Ok(())
+        })?;
+
+        Ok(conflicts)
+    }
+}
+
+fn to_object(chain: &Chain) -> Result<Map<String, Value>, Error> {
+    match serde_json::to_value(chain).map_err(error::serialize)? {
+        Value::Object(object) => Ok(object),
+        _ => unreachable!("Chain should serialize to an object"),
+    }
+}
+
+/// The elements of both arrays, the upstream ones first, without duplicates.
+fn union(upstream: &Value, newer: &Value) -> Value {
+    let mut union = upstream.as_array().cloned().unwrap_or_default();
+    for value in newer.as_array().into_iter().flatten() {
+        if !union.contains(value) {
+            union.push(value.clone());
+        }
+    }
+
+    Value::Array(union)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::error::Kind;
+
+    /// The embedded registry with a private chain and Ethereum renamed, on
+    /// an internal rpc.
+    fn internal() -> ChainRegistry {
+        let mut internal = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let mut ethereum = internal.get(1).expect("Chain(1) should exist").clone();
+        ethereum.name = "Ethereum (internal)".into();
+        ethereum.rpc = vec!["https://eth.internal.example".into()];
+        let mut private = ethereum.clone();
+        private.chain_id = u64::MAX;
+        private.short_name = "private".into();
+
+        internal
+            .transaction(|tx| {
+                tx.replace(ethereum);
+                tx.insert(private);
+                Ok(())
+            })
+            .expect("Transaction should commit");
+        internal
+    }
+
+    #[test]
+    fn prefer_upstream() {
+        let mut registry = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let upstream = registry.get(1).expect("Chain(1) should exist").clone();
+
+        let conflicts = registry
+            .merge(&internal(), MergePolicy::default())
+            .expect("Should merge the registries");
+
+        assert_eq!(
+            vec!["name", "rpc"],
+            conflicts
+                .iter()
+                .map(|conflict| conflict.field.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(json!("Ethereum (internal)"), conflicts[0].newer);
+        assert_eq!(Some(&upstream), registry.get(1));
+        assert!(registry.by_short_name("private").is_some());
+    }
+
+    #[test]
+    fn prefer_newer_with_rpc_union() {
+        let mut registry = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let mut rpc = registry.get(1).expect("Chain(1) should exist").rpc.clone();
+        rpc.push("https://eth.internal.example".into());
+
+        registry
+            .merge(
+                &internal(),
+                MergePolicy {
+                    conflicts: Resolution::PreferNewer,
+                    union_rpcs: true,
+                },
+            )
+            .expect("Should merge the registries");
+
+        let ethereum = registry.get(1).expect("Chain(1) should exist");
+        assert_eq!("Ethereum (internal)", ethereum.name);
+        assert_eq!(rpc, ethereum.rpc);
+    }
+
+    #[test]
+    fn all_or_nothing() {
+        let mut registry = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let mut other = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let mut duplicate = other.get(1).expect("Chain(1) should exist").clone();
+        duplicate.chain_id = u64::MAX;
+        other
+            .transaction(|tx| {
+                tx.remove(1);
+                tx.insert(duplicate);
+                Ok(())
+            })
+            .expect("Transaction should commit");
+
+        let error = registry
+            .merge(&other, MergePolicy::default())
+            .expect_err("The short name of Chain(1) is reused");
+        assert_eq!(Kind::Transaction, error.kind());
+        assert!(registry.get(u64::MAX).is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! Merkle-tree commitment over the chains.
+//!
+//! The leaves are the [`Chain::fingerprint`]s in ascending chain id order.
+//! Leaves are hashed as `sha256(0x00 || fingerprint)` and inner nodes as
+//! `sha256(0x01 || left || right)`, an unpaired node is carried up a level
+//! unchanged.
+use crate::{sha256::sha256, Chain, ChainRegistry};
+
+/// Which side of the path the sibling hash is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Proof that a chain record is part of the tree with a given root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionProof {
+    pub chain_id: u64,
+    /// Sibling hashes from the leaf up to the root
+    pub siblings: Vec<(Side, [u8; 32])>,
+}
+
+impl InclusionProof {
+    /// Whether the chain, as given, is included in the tree with this root.
+    pub fn verify(&self, chain: &Chain, root: &[u8; 32]) -> bool {
+        if chain.chain_id != self.chain_id {
+            return false;
+        }
+
+        let computed =
+            self.siblings
+                .iter()
+                .fold(
+                    leaf_hash(&chain.fingerprint()),
+                    |hash, (side, sibling)| match side {
+                        Side::Left => node_hash(sibling, &hash),
+                        Side::Right => node_hash(&hash, sibling),
+                    },
+                );
+
+        &computed == root
+    }
+}
+
+impl ChainRegistry {
+    /// The root of the tree over all the registry's chains.
+    pub fn merkle_root(&self) -> [u8; 32] {
+        root(&leaves(self))
+    }
+
+    /// The inclusion proof for the chain, if it exists.
+    pub fn prove(&self, chain_id: u64) -> Option<InclusionProof> {
+        let index = self.iter().position(|chain| chain.chain_id == chain_id)?;
+
+        Some(InclusionProof {
+            chain_id,
+            siblings: path(leaves(self), index),
+        })
+    }
+}
+
+/// See [`ChainRegistry::merkle_root`], all zeros if the
+/// [`ChainRegistry::global`] registry failed to load.
+pub fn merkle_root() -> [u8; 32] {
+    ChainRegistry::global()
+        .map(ChainRegistry::merkle_root)
+        .unwrap_or_default()
+}
+
+/// See [`ChainRegistry::prove`].
+pub fn prove(chain_id: u64) -> Option<InclusionProof> {
+    ChainRegistry::global().ok()?.prove(chain_id)
+}
+
+fn leaf_hash(fingerprint: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = [0_u8; 33];
+    preimage[1..].copy_from_slice(fingerprint);
+
+    sha256(&preimage)
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = [0_u8; 65];
+    preimage[0] = 0x01;
+    preimage[1..33].copy_from_slice(left);
+    preimage[33..].copy_from_slice(right);
+
+    sha256(&preimage)
+}
+
+fn leaves(registry: &ChainRegistry) -> Vec<[u8; 32]> {
+    registry
+        .iter()
+        .map(|chain| leaf_hash(&chain.fingerprint()))
+        .collect()
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [unpaired] => *unpaired,
+            _ => unreachable!("chunks are never empty"),
+        })
+        .collect()
+}
+
+/// The root of the leaves, all zeros for no leaves.
+fn root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+
+    level.first().copied().unwrap_or_default()
+}
+
+fn path(mut level: Vec<[u8; 32]>, mut index: usize) -> Vec<(Side, [u8; 32])> {
+    let mut siblings = Vec::new();
+
+    while level.len() > 1 {
+        let sibling = if index.is_multiple_of(2) {
+            level.get(index + 1).map(|hash| (Side::Right, *hash))
+        } else {
+            Some((Side::Left, level[index - 1]))
+        };
+        siblings.extend(sibling);
+
+        level = next_level(&level);
+        index /= 2;
+    }
+
+    siblings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proves_every_chain() {
+        let root = merkle_root();
+
+        for chain_id in [1, 56, 137] {
+            let chain = Chain::get(chain_id).expect("Chain should exist");
+            let proof = prove(chain_id).expect("Chain should have a proof");
+
+            assert!(proof.verify(&chain, &root));
+
+            let mut tampered = chain.clone();
+            tampered.name.push('!');
+            assert!(!proof.verify(&tampered, &root));
+        }
+
+        assert_eq!(None, prove(u64::MAX));
+
+        let registry = ChainRegistry::try_load()
+            .expect("Should load the embedded chains")
+            .retain(|chain| chain.chain_id != 56);
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let proof = registry.prove(1).expect("Chain should have a proof");
+        assert!(proof.verify(&ethereum, &registry.merkle_root()));
+        assert!(!proof.verify(&ethereum, &root));
+        assert_eq!(None, registry.prove(56));
+    }
+
+    #[test]
+    fn unbalanced_trees() {
+        let leaves = (0..5_u8).map(|leaf| [leaf; 32]).collect::<Vec<_>>();
+        let root = root(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let computed =
+                path(leaves.clone(), index)
+                    .into_iter()
+                    .fold(*leaf, |hash, (side, sibling)| match side {
+                        Side::Left => node_hash(&sibling, &hash),
+                        Side::Right => node_hash(&hash, &sibling),
+                    });
+            assert_eq!(root, computed);
+        }
+    }
+}
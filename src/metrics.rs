@@ -0,0 +1,250 @@
+//! Cost of the loaded chains in the running process.
+use std::{collections::HashMap, mem::size_of, time::Duration};
+
+use crate::{
+    registry::Indexes, status::ChainStatus, Bridge, Chain, ChainRegistry, Ens, Explorer,
+    ExplorerStandard, Feature, NativeCurrency, Parent,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegistryMetrics {
+    /// The time it took to read and deserialize all chain files
+    pub load_duration: Duration,
+    /// Estimated memory held by the loaded chains and the lookup indexes of
+    /// the registry (e.g. of [`ChainRegistry::by_name`]), including heap
+    /// allocations
+    pub bytes_estimated: usize,
+    /// Number of loaded chains
+    pub chains: usize,
+}
+
+impl ChainRegistry {
+    /// The metrics of the registry's chains.
+    pub fn metrics(&self) -> RegistryMetrics {
+        RegistryMetrics {
+            load_duration: self.load_duration(),
+            bytes_estimated: self.len() * size_of::<(u64, Chain)>()
+                + self.iter().map(HeapSize::heap_size).sum::<usize>()
+                + size_of::<Indexes>()
+                + self.indexes().heap_size(),
+            chains: self.len(),
+        }
+    }
+}
+
+/// The metrics of the [`ChainRegistry::global`] registry, loading it if it
+/// hasn't been yet, see [`ChainRegistry::metrics`].
+pub fn registry_metrics() -> RegistryMetrics {
+    ChainRegistry::global()
+        .map(ChainRegistry::metrics)
+        .unwrap_or_default()
+}
+
+/// The heap memory owned by a value, not counting its own size.
+///
+/// The structs are destructured without `..`, so a new field doesn't compile
+/// until it's accounted for.
+trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for HashMap<K, V> {
+    fn heap_size(&self) -> usize {
+        // a control byte per bucket, see `hashbrown`
+        self.capacity() * (size_of::<(K, V)>() + 1)
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+macro_rules! no_heap {
+    ($($type:ty),+) => {
+        $(impl HeapSize for $type {
+            fn heap_size(&self) -> usize {
+                0
+            }
+        })+
+    };
+}
+
+no_heap!(u64, i64, ChainStatus);
+
+impl HeapSize for Chain {
+    fn heap_size(&self) -> usize {
+        let Chain {
+            name,
+            title,
+            chain,
+            network,
+            icon,
+            rpc,
+            faucets,
+            native_currency,
+            info_url,
+            short_name,
+            chain_id,
+            network_id,
+            slip44,
+            ens,
+            explorers,
+            features,
+            parent,
+            status,
+            red_flags,
+            // attached by the application, not part of the chain data
+            ext: _,
+            overlays: _,
+        } = self;
+
+        name.heap_size()
+            + title.heap_size()
+            + chain.heap_size()
+            + network.heap_size()
+            + icon.heap_size()
+            + rpc.heap_size()
+            + faucets.heap_size()
+            + native_currency.heap_size()
+            + info_url.heap_size()
+            + short_name.heap_size()
+            + chain_id.heap_size()
+            + network_id.heap_size()
+            + slip44.heap_size()
+            + ens.heap_size()
+            + explorers.heap_size()
+            + features.heap_size()
+            + parent.heap_size()
+            + status.heap_size()
+            + red_flags.heap_size()
+    }
+}
+
+impl HeapSize for Indexes {
+    fn heap_size(&self) -> usize {
+        let Indexes {
+            names,
+            short_names,
+            network_ids,
+            currency_symbols,
+        } = self;
+
+        names.heap_size()
+            + short_names.heap_size()
+            + network_ids.heap_size()
+            + currency_symbols.heap_size()
+    }
+}
+
+impl HeapSize for NativeCurrency {
+    fn heap_size(&self) -> usize {
+        let NativeCurrency {
+            name,
+            symbol,
+            decimals,
+        } = self;
+
+        name.heap_size() + symbol.heap_size() + decimals.heap_size()
+    }
+}
+
+impl HeapSize for Ens {
+    fn heap_size(&self) -> usize {
+        let Ens { registry } = self;
+
+        registry.heap_size()
+    }
+}
+
+impl HeapSize for Explorer {
+    fn heap_size(&self) -> usize {
+        let Explorer {
+            name,
+            url,
+            standard,
+            icon,
+        } = self;
+
+        name.heap_size() + url.heap_size() + standard.heap_size() + icon.heap_size()
+    }
+}
+
+impl HeapSize for ExplorerStandard {
+    fn heap_size(&self) -> usize {
+        match self {
+            ExplorerStandard::Eip3091 | ExplorerStandard::None => 0,
+            ExplorerStandard::Other(name) => name.heap_size(),
+        }
+    }
+}
+
+impl HeapSize for Feature {
+    fn heap_size(&self) -> usize {
+        match self {
+            Feature::Eip155 | Feature::Eip1559 => 0,
+            Feature::Other(name) => name.heap_size(),
+        }
+    }
+}
+
+impl HeapSize for Parent {
+    fn heap_size(&self) -> usize {
+        let Parent {
+            kind,
+            chain,
+            bridges,
+        } = self;
+
+        kind.heap_size() + chain.heap_size() + bridges.heap_size()
+    }
+}
+
+impl HeapSize for Bridge {
+    fn heap_size(&self) -> usize {
+        let Bridge { url } = self;
+
+        url.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics() {
+        let metrics = registry_metrics();
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+
+        assert_eq!(registry.len(), metrics.chains);
+        assert!(metrics.bytes_estimated > metrics.chains * size_of::<Chain>());
+        assert_eq!(metrics, registry.metrics());
+
+        let indexes = registry.indexes().heap_size();
+        assert!(indexes > registry.len() * 4 * size_of::<u64>());
+        let chains = registry.len() * size_of::<(u64, Chain)>()
+            + registry.iter().map(HeapSize::heap_size).sum::<usize>();
+        assert_eq!(
+            chains + size_of::<Indexes>() + indexes,
+            metrics.bytes_estimated
+        );
+    }
+}
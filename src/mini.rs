@@ -0,0 +1,82 @@
+//! The reduced field set which upstream publishes as `chains_mini.json`, as
+//! static data generated at build time, for size-constrained builds.
+//!
+//! ```
+//! use evm_chains::mini::ChainMini;
+//!
+//! let ethereum = ChainMini::get(1).expect("Chain(1) should exist");
+//! assert_eq!("ETH", ethereum.native_currency.symbol);
+//! ```
+//!
+//! With the `full` feature, [`ChainMini::chain`] upgrades to the full `Chain`.
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/mini.rs"));
+}
+
+use crate::tables::index_of;
+
+/// The `chains_mini.json` fields of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ChainMini {
+    pub name: &'static str,
+    pub chain_id: u64,
+    pub short_name: &'static str,
+    pub rpc: &'static [&'static str],
+    pub native_currency: NativeCurrencyMini,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct NativeCurrencyMini {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub decimals: u8,
+}
+
+impl ChainMini {
+    pub fn get(chain_id: u64) -> Option<&'static ChainMini> {
+        index_of(chain_id).map(|index| &generated::CHAINS[index])
+    }
+
+    /// All the chains in ascending chain id order.
+    pub fn iter() -> impl ExactSizeIterator<Item = &'static ChainMini> + DoubleEndedIterator {
+        generated::CHAINS.iter()
+    }
+
+    /// The full [`Chain`](crate::Chain) with the same chain id, if it's loaded.
+    #[cfg(feature = "full")]
+    pub fn chain(&self) -> Option<&'static crate::Chain> {
+        crate::registry::chains().get(&self.chain_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mini_chains() {
+        let ethereum = ChainMini::get(1).expect("Chain(1) should exist");
+        assert_eq!(Some(ethereum), ChainMini::iter().next());
+        assert_eq!(None, ChainMini::get(u64::MAX));
+
+        #[cfg(feature = "full")]
+        {
+            let chain = ethereum.chain().expect("Chain(1) should be loaded");
+
+            assert_eq!(chain.name, ethereum.name);
+            assert_eq!(chain.short_name, ethereum.short_name);
+            assert_eq!(chain.rpc, ethereum.rpc);
+            assert_eq!(chain.native_currency.name, ethereum.native_currency.name);
+            assert_eq!(
+                chain.native_currency.symbol,
+                ethereum.native_currency.symbol
+            );
+            assert_eq!(
+                chain.native_currency.decimals,
+                i64::from(ethereum.native_currency.decimals)
+            );
+        }
+    }
+}
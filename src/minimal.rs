@@ -0,0 +1,46 @@
+//! Lookups over tables generated at build time, without the [`Chain`](crate::Chain)
+//! struct, `serde` or any initialization at runtime.
+//!
+//! ```
+//! const NAME: Option<&str> = evm_chains::chain_name(1);
+//! ```
+use crate::tables::{index_of, DECIMALS, NAMES, SYMBOLS};
+
+/// E.g. `chain_name(1) == Some("Ethereum Mainnet")`
+pub const fn chain_name(chain_id: u64) -> Option<&'static str> {
+    match index_of(chain_id) {
+        Some(index) => Some(NAMES[index].1),
+        None => None,
+    }
+}
+
+/// The native currency symbol, e.g. `chain_symbol(1) == Some("ETH")`
+pub const fn chain_symbol(chain_id: u64) -> Option<&'static str> {
+    match index_of(chain_id) {
+        Some(index) => Some(SYMBOLS[index].1),
+        None => None,
+    }
+}
+
+/// The native currency decimals, e.g. `chain_decimals(1) == Some(18)`
+pub const fn chain_decimals(chain_id: u64) -> Option<u8> {
+    match index_of(chain_id) {
+        Some(index) => Some(DECIMALS[index].1),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_lookups() {
+        const ETHEREUM: (Option<&str>, Option<&str>, Option<u8>) =
+            (chain_name(1), chain_symbol(1), chain_decimals(1));
+
+        assert_eq!((Some("Ethereum Mainnet"), Some("ETH"), Some(18)), ETHEREUM);
+        assert_eq!(Some("BNB"), chain_symbol(56));
+        assert_eq!(None, chain_name(u64::MAX));
+    }
+}
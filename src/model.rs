@@ -0,0 +1,168 @@
+//! Versioned (de)serialization of the [`Chain`] data model.
+//!
+//! Services which persist serialized [`Chain`]s should store the
+//! [`MODEL_VERSION`] next to them and read them back with
+//! [`Chain::from_versioned_json`], which migrates payloads of older data
+//! model versions.
+//!
+//! | version | changes                          |
+//! |---------|----------------------------------|
+//! | 1       | initial data model               |
+//! | 2       | `features` added                 |
+//! | 3       | `parent` added                   |
+//! | 4       | `status` added                   |
+//! | 5       | `redFlags` added                 |
+//! | 6       | `title` added                    |
+//! | 7       | `explorers[].icon` added         |
+//!
+//! The data model structs are `#[non_exhaustive]`: outside the crate they
+//! are built with their constructors (e.g. [`Chain::new`]) and by assigning
+//! the public fields, never with struct literals, so new fields are not a
+//! breaking change, only a new [`MODEL_VERSION`].
+use serde_json::Value;
+
+use crate::{error, Chain, Error};
+
+/// The version of the [`Chain`] data model of this crate.
+pub const MODEL_VERSION: u32 = 7;
+
+/// Migrates a payload of version `N` to version `N + 1`, indexed by `N - 1`.
+const MIGRATIONS: [fn(&mut Value); MODEL_VERSION as usize - 1] =
+    [v1_to_v2, v2_to_v3, v3_to_v4, v4_to_v5, v5_to_v6, v6_to_v7];
+
+fn v1_to_v2(chain: &mut Value) {
+    if let Value::Object(object) = chain {
+        object
+            .entry("features")
+            .or_insert_with(|| Value::Array(Vec::new()));
+    }
+}
+
+fn v2_to_v3(chain: &mut Value) {
+    if let Value::Object(object) = chain {
+        object.entry("parent").or_insert(Value::Null);
+    }
+}
+
+fn v3_to_v4(chain: &mut Value) {
+    if let Value::Object(object) = chain {
+        object
+            .entry("status")
+            .or_insert_with(|| Value::String("active".into()));
+    }
+}
+
+fn v4_to_v5(chain: &mut Value) {
+    if let Value::Object(object) = chain {
+        object
+            .entry("redFlags")
+            .or_insert_with(|| Value::Array(Vec::new()));
+    }
+}
+
+fn v5_to_v6(chain: &mut Value) {
+    if let Value::Object(object) = chain {
+        object.entry("title").or_insert(Value::Null);
+    }
+}
+
+fn v6_to_v7(chain: &mut Value) {
+    if let Some(Value::Array(explorers)) = chain.get_mut("explorers") {
+        for explorer in explorers.iter_mut().filter_map(Value::as_object_mut) {
+            explorer.entry("icon").or_insert(Value::Null);
+        }
+    }
+}
+
+/// The version of the [`Chain`] data model of this crate, see [`MODEL_VERSION`].
+pub fn model_version() -> u32 {
+    MODEL_VERSION
+}
+
+/// Checks whether payloads with data model version `required` (e.g. from a
+/// plugin compiled against another version of the crate) can be read by
+/// this crate.
+///
+/// Older payloads are migrated by [`Chain::from_versioned_json`], newer ones
+/// are rejected, so two parties can interchange payloads in both directions
+/// only if both checks succeed.
+pub fn check_compat(required: u32) -> Result<(), Error> {
+    match required {
+        1..=MODEL_VERSION => Ok(()),
+        _ => Err(error::model_version(required)),
+    }
+}
+
+impl Chain {
+    /// Deserializes a chain persisted with data model `version`, migrating
+    /// it to the current [`MODEL_VERSION`].
+    pub fn from_versioned_json(version: u32, bytes: &[u8]) -> Result<Self, Error> {
+        check_compat(version)?;
+
+        let mut value = serde_json::from_slice::<Value>(bytes).map_err(error::deserialize)?;
+        for migration in &MIGRATIONS[version as usize - 1..] {
+            migration(&mut value);
+        }
+
+        serde_json::from_value(value).map_err(error::deserialize)
+    }
+
+    /// Serializes the chain with the current [`MODEL_VERSION`].
+    pub fn to_versioned_json(&self) -> (u32, Vec<u8>) {
+        (
+            MODEL_VERSION,
+            serde_json::to_vec(self).expect("Chain should serialize to JSON"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Kind;
+
+    #[test]
+    fn versioned_round_trip() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let (version, bytes) = ethereum.to_versioned_json();
+
+        assert_eq!(MODEL_VERSION, version);
+        assert_eq!(
+            ethereum,
+            Chain::from_versioned_json(version, &bytes).expect("Should deserialize")
+        );
+    }
+
+    #[test]
+    fn migrates_v1() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let mut v1 = serde_json::to_value(&ethereum).unwrap();
+        v1.as_object_mut().unwrap().remove("features");
+        v1.as_object_mut().unwrap().remove("parent");
+        v1.as_object_mut().unwrap().remove("status");
+        v1.as_object_mut().unwrap().remove("redFlags");
+        v1.as_object_mut().unwrap().remove("title");
+
+        let migrated = Chain::from_versioned_json(1, &serde_json::to_vec(&v1).unwrap())
+            .expect("Should migrate");
+        assert_eq!(Vec::<crate::Feature>::new(), migrated.features);
+        assert_eq!(ethereum.name, migrated.name);
+    }
+
+    #[test]
+    fn compatibility() {
+        assert_eq!(MODEL_VERSION, model_version());
+        assert!(check_compat(1).is_ok());
+        assert!(check_compat(MODEL_VERSION).is_ok());
+        assert!(check_compat(0).is_err());
+        assert!(check_compat(MODEL_VERSION + 1).is_err());
+    }
+
+    #[test]
+    fn unknown_version() {
+        let error = Chain::from_versioned_json(MODEL_VERSION + 1, b"{}")
+            .expect_err("Future versions can't be read");
+
+        assert_eq!(Kind::ModelVersion, error.kind());
+    }
+}
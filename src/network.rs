@@ -0,0 +1,151 @@
+//! Telling testnets and mainnets apart, also at the type level with the
+//! [`Mainnet`] and [`Testnet`] wrappers:
+//!
+//! ```
+//! use evm_chains::{network::Mainnet, Chain};
+//!
+//! fn deploy(chain: &Mainnet<&Chain>) -> u64 {
+//!     chain.chain_id
+//! }
+//!
+//! let ethereum = Chain::mainnets().next().expect("Should have a mainnet");
+//! assert_eq!(ethereum.chain_id, deploy(&ethereum));
+//! ```
+use std::{borrow::Borrow, ops::Deref};
+
+use crate::{testnet, Chain};
+
+macro_rules! network_wrapper {
+    ($(#[$doc:meta])* $name:ident, $is_testnet:literal) => {
+        $(#[$doc])*
+        ///
+        /// Derefs to the [`Chain`], where `C` is either an owned [`Chain`] or a
+        /// reference to one.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $name<C = Chain>(C);
+
+        impl<C: Borrow<Chain>> $name<C> {
+            /// Wraps the chain, or returns it back if it's on the other network.
+            pub fn new(chain: C) -> Result<Self, C> {
+                if chain.borrow().is_testnet() == $is_testnet {
+                    Ok(Self(chain))
+                } else {
+                    Err(chain)
+                }
+            }
+
+            pub fn into_inner(self) -> C {
+                self.0
+            }
+        }
+
+        impl<C: Borrow<Chain>> Deref for $name<C> {
+            type Target = Chain;
+
+            fn deref(&self) -> &Chain {
+                self.0.borrow()
+            }
+        }
+    };
+}
+
+network_wrapper!(
+    /// A chain for which [`Chain::is_testnet`] is `false`.
+    Mainnet,
+    false
+);
+network_wrapper!(
+    /// A chain for which [`Chain::is_testnet`] is `true`.
+    Testnet,
+    true
+);
+
+/// A chain classified by [`Chain::classify`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Network<C = Chain> {
+    Mainnet(Mainnet<C>),
+    Testnet(Testnet<C>),
+}
+
+impl<C: Borrow<Chain>> Network<C> {
+    pub fn into_inner(self) -> C {
+        match self {
+            Network::Mainnet(mainnet) => mainnet.into_inner(),
+            Network::Testnet(testnet) => testnet.into_inner(),
+        }
+    }
+}
+
+impl<C: Borrow<Chain>> From<C> for Network<C> {
+    fn from(chain: C) -> Self {
+        match Testnet::new(chain) {
+            Ok(testnet) => Network::Testnet(testnet),
+            Err(chain) => Network::Mainnet(Mainnet(chain)),
+        }
+    }
+}
+
+impl Chain {
+    /// Wraps the chain in a [`Mainnet`] or [`Testnet`], see [`Chain::is_testnet`].
+    pub fn classify(self) -> Network {
+        Network::from(self)
+    }
+
+    /// Whether the chain is a testnet, e.g. its `network` is not `mainnet` or
+    /// its name contains `testnet`, `goerli` or `sepolia`.
+    ///
+    /// Only when the `network` is empty, having faucets makes it a testnet.
+    ///
+    /// The `EVM_CHAINS_FILTER` `testnet` condition uses the same definition.
+    pub fn is_testnet(&self) -> bool {
+        testnet::is_testnet(&self.name, &self.network, !self.faucets.is_empty())
+    }
+
+    /// All the mainnets in ascending chain id order.
+    pub fn mainnets() -> impl DoubleEndedIterator<Item = Mainnet<&'static Chain>> {
+        Chain::iter().filter_map(|chain| Mainnet::new(chain).ok())
+    }
+
+    /// All the testnets in ascending chain id order.
+    pub fn testnets() -> impl DoubleEndedIterator<Item = Testnet<&'static Chain>> {
+        Chain::iter().filter_map(|chain| Testnet::new(chain).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mainnets_and_testnets() {
+        for chain_id in [1, 56, 137] {
+            let chain = Chain::get(chain_id).expect("Chain should exist");
+
+            assert!(!chain.is_testnet());
+            assert!(Chain::mainnets().any(|mainnet| *mainnet == chain));
+        }
+
+        assert!(Chain::testnets().all(|testnet| testnet.is_testnet()));
+        assert_eq!(
+            Chain::iter().len(),
+            Chain::mainnets().count() + Chain::testnets().count()
+        );
+    }
+
+    #[test]
+    fn network_wrappers() {
+        let sepolia = Chain::get(11155111).expect("Chain(11155111) should exist");
+        let sepolia = Mainnet::new(sepolia).expect_err("Sepolia is a testnet");
+
+        match sepolia.clone().classify() {
+            Network::Testnet(testnet) => assert_eq!(sepolia, *testnet),
+            Network::Mainnet(_) => panic!("Sepolia should be classified as a testnet"),
+        }
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let mainnet = Mainnet::new(&ethereum).expect("Chain(1) is a mainnet");
+        assert_eq!(1, mainnet.chain_id);
+        assert!(Testnet::new(&ethereum).is_err());
+        assert_eq!(&ethereum, Network::from(&ethereum).into_inner());
+    }
+}
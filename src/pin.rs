@@ -0,0 +1,219 @@
+//! Invariants for the chains a product depends on.
+//!
+//! A [`Pin`] declares what a critical chain must look like, e.g. the native
+//! currency symbol of Ethereum Mainnet being `ETH`. The pins of a
+//! [`RegistryBuilder`] are checked when the chains are loaded, which turns an
+//! upstream data regression into a hard failure instead of a subtle bug.
+//!
+//! ```no_run
+//! use evm_chains::{pin, ChainRegistry};
+//!
+//! let registry = ChainRegistry::builder()
+//!     .pins([
+//!         pin!(1, native_symbol = "ETH", explorer_host = "etherscan.io"),
+//!         pin!(137, native_symbol = "POL"),
+//!     ])
+//!     .load()
+//!     .expect("Pinned chains should hold");
+//! ```
+use std::fmt;
+
+use crate::{error, url, Chain, ChainRegistry, Error, RegistryBuilder};
+
+/// Create a [`Pin`] for a chain id with the given invariants.
+///
+/// Every `field = value` pair calls the [`Pin`] method with the same name.
+#[macro_export]
+macro_rules! pin {
+    ($chain_id:expr $(, $invariant:ident = $value:expr)* $(,)?) => {
+        $crate::pin::Pin::new($chain_id)$(.$invariant($value))*
+    };
+}
+
+/// Invariants which a given chain should satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pin {
+    pub chain_id: u64,
+    /// The expected [`NativeCurrency::symbol`](crate::NativeCurrency::symbol)
+    pub native_symbol: Option<String>,
+    /// A host (or parent domain) which at least one explorer should be served from
+    pub explorer_host: Option<String>,
+}
+
+impl Pin {
+    /// A pin which only requires the chain to exist.
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            native_symbol: None,
+            explorer_host: None,
+        }
+    }
+
+    pub fn native_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.native_symbol = Some(symbol.into());
+        self
+    }
+
+    pub fn explorer_host(mut self, host: impl Into<String>) -> Self {
+        self.explorer_host = Some(host.into());
+        self
+    }
+
+    /// Checks the pin against a chain (or lack of one).
+    pub fn check(&self, chain: Option<&Chain>) -> Result<(), Violation> {
+        let chain = chain.ok_or(Violation::Missing {
+            chain_id: self.chain_id,
+        })?;
+
+        if let Some(expected) = &self.native_symbol {
+            if &chain.native_currency.symbol != expected {
+                return Err(Violation::NativeSymbol {
+                    chain_id: self.chain_id,
+                    expected: expected.clone(),
+                    actual: chain.native_currency.symbol.clone(),
+                });
+            }
+        }
+
+        if let Some(expected) = &self.explorer_host {
+            let found = chain.explorers.iter().any(|explorer| {
+                url::host(&explorer.url)
+                    .is_some_and(|host| url::is_same_or_subdomain(host, expected))
+            });
+
+            if !found {
+                return Err(Violation::ExplorerHost {
+                    chain_id: self.chain_id,
+                    expected: expected.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A pinned invariant which doesn't hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    Missing {
+        chain_id: u64,
+    },
+    NativeSymbol {
+        chain_id: u64,
+        expected: String,
+        actual: String,
+    },
+    ExplorerHost {
+        chain_id: u64,
+        expected: String,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Missing { chain_id } => write!(f, "Chain({}) does not exist", chain_id),
+            Violation::NativeSymbol {
+                chain_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Chain({}) native currency symbol is {:?}, expected {:?}",
+                chain_id, actual, expected
+            ),
+            Violation::ExplorerHost { chain_id, expected } => write!(
+                f,
+                "Chain({}) has no explorer on host {:?}",
+                chain_id, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+impl Violation {
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Violation::Missing { chain_id }
+            | Violation::NativeSymbol { chain_id, .. }
+            | Violation::ExplorerHost { chain_id, .. } => *chain_id,
+        }
+    }
+}
+
+impl RegistryBuilder {
+    /// Makes [`RegistryBuilder::load`] fail with the first violated pin.
+    pub fn pins(mut self, pins: impl IntoIterator<Item = Pin>) -> Self {
+        self.rules.pins.extend(pins);
+        self
+    }
+}
+
+impl ChainRegistry {
+    /// Checks all pins against the chains and fails on the first violation.
+    pub fn check_pins(&self, pins: &[Pin]) -> Result<(), Error> {
+        pins.iter()
+            .try_for_each(|pin| pin.check(self.get(pin.chain_id)))
+            .map_err(error::pin_violation)
+    }
+}
+
+/// Checks all pins against the chains of the [`ChainRegistry::global`]
+/// registry, see [`ChainRegistry::check_pins`]. Fails with the error of the
+/// global registry if it failed to load.
+///
+/// Prefer [`RegistryBuilder::pins`], which checks them when the chains are loaded.
+pub fn check_pins(pins: &[Pin]) -> Result<(), Error> {
+    ChainRegistry::global()
+        .map_err(Error::clone)?
+        .check_pins(pins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_chains() {
+        check_pins(&[
+            pin!(1, native_symbol = "ETH", explorer_host = "etherscan.io"),
+            pin!(56, native_symbol = "BNB"),
+            pin!(137),
+        ])
+        .expect("Pins should hold");
+    }
+
+    #[test]
+    fn violated_pins() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert!(matches!(
+            pin!(1, native_symbol = "BTC").check(Some(&ethereum)),
+            Err(Violation::NativeSymbol { .. })
+        ));
+        assert!(matches!(
+            pin!(1, explorer_host = "example.com").check(Some(&ethereum)),
+            Err(Violation::ExplorerHost { .. })
+        ));
+        assert_eq!(Err(Violation::Missing { chain_id: 1 }), pin!(1).check(None));
+    }
+
+    #[test]
+    fn pins_at_load() {
+        ChainRegistry::builder()
+            .pins([pin!(1, native_symbol = "ETH")])
+            .load()
+            .expect("Pins should hold");
+
+        let error = ChainRegistry::builder()
+            .pins([pin!(137), pin!(1, native_symbol = "BTC")])
+            .load()
+            .expect_err("Pin should be violated");
+        assert_eq!(crate::error::Kind::Pin, error.kind());
+        assert_eq!(Some(1), error.chain_id());
+    }
+}
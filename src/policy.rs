@@ -0,0 +1,355 @@
+//! Policies applied to the chain data.
+//!
+//! A [`ContentPolicy`] for constrained displays (e.g. hardware wallets) reports
+//! the [`Lint`]s of a chain which violates it and, when enforced, rejects the
+//! chain altogether. Set with [`RegistryBuilder::content_policy`], it's applied
+//! while the chains load and an enforced policy leaves the violating chains
+//! out of the registry.
+//!
+//! A [`UrlPolicy`] enforces a transport policy on all URL accessors, e.g.
+//! [`Chain::rpc_urls`], set with [`RegistryBuilder::url_policy`].
+use std::fmt;
+
+use crate::{
+    error,
+    lint::{Lint, LintKind},
+    registry, url, Chain, ChainRegistry, Error, RegistryBuilder,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContentPolicy {
+    /// Maximum number of characters in [`Chain::name`]
+    pub max_name_length: Option<usize>,
+    /// Disallow emoji in [`Chain::name`] and [`Chain::short_name`]
+    pub deny_emoji: bool,
+    /// Allow only printable ASCII characters without whitespace in [`Chain::short_name`]
+    pub ascii_short_name: bool,
+    /// Make [`ContentPolicy::apply`] fail on any violation and
+    /// [`RegistryBuilder::load`] leave out the violating chains, instead of
+    /// only reporting them
+    pub enforce: bool,
+}
+
+/// The rule of a [`ContentPolicy`] which was violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    MaxLength(usize),
+    Emoji,
+    AsciiShortName,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::MaxLength(max) => write!(f, "longer than {} characters", max),
+            Rule::Emoji => f.write_str("contains emoji"),
+            Rule::AsciiShortName => f.write_str("is not printable ASCII"),
+        }
+    }
+}
+
+impl ContentPolicy {
+    /// Checks the chain and returns a [`Lint`] for every violated rule.
+    pub fn check(&self, chain: &Chain) -> Vec<Lint> {
+        let mut violations = Vec::new();
+
+        if let Some(max) = self.max_name_length {
+            if chain.name.chars().count() > max {
+                violations.push(("name", Rule::MaxLength(max)));
+            }
+        }
+
+        if self.deny_emoji {
+            if chain.name.chars().any(is_emoji) {
+                violations.push(("name", Rule::Emoji));
+            }
+            if chain.short_name.chars().any(is_emoji) {
+                violations.push(("shortName", Rule::Emoji));
+            }
+        }
+
+        if self.ascii_short_name && !chain.short_name.chars().all(|c| c.is_ascii_graphic()) {
+            violations.push(("shortName", Rule::AsciiShortName));
+        }
+
+        violations
+            .into_iter()
+            .map(|(field, rule)| Lint {
+                chain_id: chain.chain_id,
+                kind: LintKind::ContentPolicy { field, rule },
+            })
+            .collect()
+    }
+
+    /// Checks the chain and, if the policy is enforced, fails on the first violation.
+    ///
+    /// The lints of a non-enforced policy are returned instead.
+    pub fn apply(&self, chain: &Chain) -> Result<Vec<Lint>, Error> {
+        let lints = self.check(chain);
+
+        match (self.enforce, lints.first()) {
+            (true, Some(violation)) => Err(error::content_policy(violation.clone())),
+            _ => Ok(lints),
+        }
+    }
+}
+
+/// Whether a class of URLs is allowed by a [`UrlPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Selection {
+    #[default]
+    Allow,
+    Exclude,
+    /// Allow only the URLs of this class
+    Only,
+}
+
+impl Selection {
+    fn allows(self, is_member: bool) -> bool {
+        match self {
+            Selection::Allow => true,
+            Selection::Exclude => !is_member,
+            Selection::Only => is_member,
+        }
+    }
+}
+
+/// Transport policy for the URLs of the chains.
+///
+/// The default policy allows all URLs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UrlPolicy {
+    /// Tor hidden services, i.e. `.onion` hosts
+    pub onion: Selection,
+    /// Unencrypted `http://` and `ws://` URLs
+    pub plain_http: Selection,
+}
+
+impl UrlPolicy {
+    /// Only encrypted, non-Tor URLs.
+    pub const TLS_ONLY: Self = Self {
+        onion: Selection::Exclude,
+        plain_http: Selection::Exclude,
+    };
+
+    /// Only `.onion` URLs.
+    pub const ONION_ONLY: Self = Self {
+        onion: Selection::Only,
+        plain_http: Selection::Allow,
+    };
+
+    pub fn allows(&self, url: &str) -> bool {
+        // compared as bytes, hosts of the chain files aren't necessarily ASCII
+        let is_onion = url::host(url).is_some_and(|host| {
+            host.len() > ".onion".len()
+                && host.as_bytes()[host.len() - ".onion".len()..].eq_ignore_ascii_case(b".onion")
+        });
+        let is_plain_http = url.split_once("://").is_some_and(|(scheme, _)| {
+            scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("ws")
+        });
+
+        self.onion.allows(is_onion) && self.plain_http.allows(is_plain_http)
+    }
+}
+
+impl RegistryBuilder {
+    /// Applies the policy to every chain while loading: the violations are
+    /// added to the [`ChainRegistry::lints`] and, if the policy is enforced,
+    /// the violating chains are left out of the registry.
+    pub fn content_policy(mut self, policy: ContentPolicy) -> Self {
+        self.rules.content_policy = policy;
+        self
+    }
+
+    /// The transport policy of the URL accessors of the registry's chains,
+    /// e.g. [`UrlPolicy::TLS_ONLY`] for a backend which must not leak
+    /// requests over plain HTTP. By default all URLs are allowed.
+    pub fn url_policy(mut self, policy: UrlPolicy) -> Self {
+        self.overlays.url_policy = policy;
+        self
+    }
+}
+
+impl ChainRegistry {
+    /// The policy of the URL accessors, see [`RegistryBuilder::url_policy`].
+    pub fn url_policy(&self) -> &UrlPolicy {
+        &self.overlays().url_policy
+    }
+}
+
+/// See [`ChainRegistry::url_policy`].
+pub fn url_policy() -> &'static UrlPolicy {
+    &registry::overlays().url_policy
+}
+
+impl Chain {
+    /// The [`Explorer::url`](crate::Explorer::url)s allowed by the url policy
+    /// of the registry the chain was loaded by.
+    pub fn explorer_urls(&self) -> impl Iterator<Item = &str> {
+        self.explorers
+            .iter()
+            .map(|explorer| explorer.url.as_str())
+            .filter(|explorer_url| self.overlays.url_policy.allows(explorer_url))
+    }
+
+    /// The [`Chain::faucets`] allowed by the url policy of the registry the
+    /// chain was loaded by.
+    pub fn faucet_urls(&self) -> impl Iterator<Item = &str> {
+        self.faucets
+            .iter()
+            .map(String::as_str)
+            .filter(|faucet_url| self.overlays.url_policy.allows(faucet_url))
+    }
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c,
+        '\u{1F000}'..='\u{1FAFF}'
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{FE0F}'
+        | '\u{20E3}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hardware_wallet() -> ContentPolicy {
+        ContentPolicy {
+            max_name_length: Some(16),
+            deny_emoji: true,
+            ascii_short_name: true,
+            enforce: true,
+        }
+    }
+
+    #[test]
+    fn ethereum_passes_policy() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert_eq!(
+            Vec::<Lint>::new(),
+            hardware_wallet().apply(&ethereum).unwrap()
+        );
+    }
+
+    #[test]
+    fn violations() {
+        let mut chain = Chain::get(1).expect("Chain(1) should exist");
+        chain.name = "Ethereum Mainnet 🚀".into();
+        chain.short_name = "eth mainnet".into();
+
+        let rules = hardware_wallet()
+            .check(&chain)
+            .into_iter()
+            .map(|lint| match lint.kind {
+                LintKind::ContentPolicy { field, rule } => (field, rule),
+                kind => panic!("Unexpected lint {:?}", kind),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("name", Rule::MaxLength(16)),
+                ("name", Rule::Emoji),
+                ("shortName", Rule::AsciiShortName)
+            ],
+            rules
+        );
+
+        assert!(hardware_wallet().apply(&chain).is_err());
+        let reporting = ContentPolicy {
+            enforce: false,
+            ..hardware_wallet()
+        };
+        assert_eq!(3, reporting.apply(&chain).unwrap().len());
+    }
+
+    #[test]
+    fn content_policy_at_load() {
+        let strict = ContentPolicy {
+            max_name_length: Some(1),
+            ..hardware_wallet()
+        };
+
+        let registry = ChainRegistry::builder()
+            .content_policy(strict.clone())
+            .load()
+            .expect("Should load the embedded chains");
+        assert!(
+            registry.is_empty(),
+            "Every chain name is longer than 1 character"
+        );
+
+        let registry = ChainRegistry::builder()
+            .content_policy(ContentPolicy {
+                enforce: false,
+                ..strict
+            })
+            .load()
+            .expect("Should load the embedded chains");
+        let violations = registry
+            .lints()
+            .iter()
+            .filter(|lint| matches!(lint.kind, LintKind::ContentPolicy { .. }))
+            .count();
+        assert!(violations >= registry.len());
+    }
+
+    #[test]
+    fn enforced_policy_drops_violating_chains() {
+        let registry = ChainRegistry::builder()
+            .content_policy(hardware_wallet())
+            .load()
+            .expect("Should load the embedded chains");
+
+        // "BNB Smart Chain Mainnet" is longer than 16 characters
+        assert!(registry.get(56).is_none());
+        assert!(registry.get(1).is_some());
+        assert!(registry.get(137).is_some());
+        assert!(registry.lints().contains(&Lint {
+            chain_id: 56,
+            kind: LintKind::ContentPolicy {
+                field: "name",
+                rule: Rule::MaxLength(16),
+            },
+        }));
+    }
+
+    #[test]
+    fn url_policies() {
+        let onion = "http://rpcxyzexampleonionaddress.onion/rpc";
+        let plain = "http://rpc.example.com";
+        let tls = "https://rpc.example.com";
+
+        assert!([onion, plain, tls]
+            .iter()
+            .all(|url| UrlPolicy::default().allows(url)));
+
+        assert!(!UrlPolicy::TLS_ONLY.allows(onion));
+        assert!(!UrlPolicy::TLS_ONLY.allows(plain));
+        assert!(UrlPolicy::TLS_ONLY.allows(tls));
+
+        assert!(UrlPolicy::ONION_ONLY.allows(onion));
+        assert!(!UrlPolicy::ONION_ONLY.allows(plain));
+        assert!(!UrlPolicy::ONION_ONLY.allows("wss://example.com/onion"));
+
+        assert!(UrlPolicy::TLS_ONLY.allows("https://aé12345/"));
+        assert!(!UrlPolicy::ONION_ONLY.allows("https://aé12345/"));
+    }
+
+    #[test]
+    fn registry_url_policy() {
+        let registry = ChainRegistry::builder()
+            .url_policy(UrlPolicy::ONION_ONLY)
+            .load()
+            .expect("Should load the embedded chains");
+        let ethereum = registry.get(1).expect("Chain(1) should exist");
+
+        assert_eq!(&UrlPolicy::ONION_ONLY, registry.url_policy());
+        assert_eq!(0, ethereum.rpc_urls().count());
+        assert_eq!(0, ethereum.explorer_urls().count());
+        assert!(Chain::get(1).unwrap().explorer_urls().count() > 0);
+    }
+}
@@ -0,0 +1,326 @@
+//! Declarative filtering of the chains.
+//!
+//! ```
+//! use evm_chains::query::ChainQuery;
+//!
+//! let chains = ChainQuery::new()
+//!     .testnet(false)
+//!     .currency("ETH")
+//!     .execute();
+//!
+//! assert!(chains.iter().all(|chain| chain.native_currency.symbol == "ETH"));
+//! ```
+//!
+//! User provided filters, e.g. of a CLI or an HTTP API, are parsed by
+//! [`ChainQuery::parse`] from a small query language:
+//!
+//! ```text
+//! query := term ("AND" term)*
+//! term  := "NOT" term | "mainnet" | "testnet" | "deprecated" | "flagged"
+//!        | "has:ens" | "has:explorer" | "tag:" TAG
+//!        | "currency:" SYMBOL | "family:" FAMILY | "feature:" NAME
+//! ```
+//!
+//! Terms are separated by whitespace, the keywords ignore case and `AND`
+//! is optional, e.g. `mainnet AND currency:ETH AND has:explorer`.
+use crate::{error, Chain, ChainRegistry, Error, Feature};
+
+/// Criteria which all the returned chains should match, unset criteria match
+/// any chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainQuery {
+    /// See [`Chain::is_testnet`]
+    pub testnet: Option<bool>,
+    pub has_ens: Option<bool>,
+    /// Whether the chain has any [`Chain::explorers`]
+    pub has_explorer: Option<bool>,
+    /// See [`Chain::is_deprecated`]
+    pub deprecated: Option<bool>,
+    /// See [`Chain::is_flagged`]
+    pub flagged: Option<bool>,
+    /// The native currency symbol
+    pub currency: Option<String>,
+    /// The [`Chain::chain`] family, e.g. `ETH`
+    pub family: Option<String>,
+    pub feature: Option<Feature>,
+    /// Tags of the [`ChainRegistry::tag_overlay`] which the chains should have
+    pub with_tags: Vec<String>,
+    /// Tags of the [`ChainRegistry::tag_overlay`] which the chains should not have
+    pub without_tags: Vec<String>,
+}
+
+impl ChainQuery {
+    /// A query matching all the chains.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn testnet(mut self, testnet: bool) -> Self {
+        self.testnet = Some(testnet);
+        self
+    }
+
+    pub fn has_ens(mut self, has_ens: bool) -> Self {
+        self.has_ens = Some(has_ens);
+        self
+    }
+
+    pub fn has_explorer(mut self, has_explorer: bool) -> Self {
+        self.has_explorer = Some(has_explorer);
+        self
+    }
+
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = Some(deprecated);
+        self
+    }
+
+    pub fn flagged(mut self, flagged: bool) -> Self {
+        self.flagged = Some(flagged);
+        self
+    }
+
+    pub fn currency(mut self, symbol: impl Into<String>) -> Self {
+        self.currency = Some(symbol.into());
+        self
+    }
+
+    pub fn family(mut self, family: impl Into<String>) -> Self {
+        self.family = Some(family.into());
+        self
+    }
+
+    pub fn feature(mut self, feature: Feature) -> Self {
+        self.feature = Some(feature);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.with_tags.push(tag.into());
+        self
+    }
+
+    pub fn without_tag(mut self, tag: impl Into<String>) -> Self {
+        self.without_tags.push(tag.into());
+        self
+    }
+
+    pub fn matches(&self, chain: &Chain) -> bool {
+        self.testnet
+            .is_none_or(|testnet| chain.is_testnet() == testnet)
+            && self
+                .has_ens
+                .is_none_or(|has_ens| chain.ens.is_some() == has_ens)
+            && self
+                .has_explorer
+                .is_none_or(|has_explorer| chain.explorers.is_empty() != has_explorer)
+            && self
+                .deprecated
+                .is_none_or(|deprecated| chain.is_deprecated() == deprecated)
+            && self
+                .flagged
+                .is_none_or(|flagged| chain.is_flagged() == flagged)
+            && self
+                .currency
+                .as_ref()
+                .is_none_or(|symbol| &chain.native_currency.symbol == symbol)
+            && self
+                .family
+                .as_ref()
+                .is_none_or(|family| &chain.chain == family)
+            && self
+                .feature
+                .as_ref()
+                .is_none_or(|feature| chain.has_feature(feature))
+            && self.with_tags.iter().all(|tag| chain.has_tag(tag))
+            && !self.without_tags.iter().any(|tag| chain.has_tag(tag))
+    }
+
+    /// Parses the query language of the [module](self), failing on unknown
+    /// terms and on the ones which contradict each other, e.g.
+    /// `mainnet AND testnet`.
+    pub fn parse(query: &str) -> Result<Self, Error> {
+        let mut parsed = Self::new();
+        let mut terms = query.split_whitespace().peekable();
+
+        while let Some(term) = terms.next() {
+            let mut negated = false;
+            let mut term = term;
+            while term.eq_ignore_ascii_case("NOT") {
+                negated = !negated;
+                term = terms
+                    .next()
+                    .ok_or_else(|| error::query(query, "expected a term after `NOT`"))?;
+            }
+            parsed
+                .add_term(term, negated)
+                .map_err(|reason| error::query(query, reason))?;
+
+            match terms.peek() {
+                Some(and) if and.eq_ignore_ascii_case("AND") => {
+                    terms.next();
+                    if terms.peek().is_none() {
+                        return Err(error::query(query, "expected a term after `AND`"));
+                    }
+                }
+                Some(or) if or.eq_ignore_ascii_case("OR") => {
+                    return Err(error::query(query, "`OR` is not supported"))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn add_term(&mut self, term: &str, negated: bool) -> Result<(), String> {
+        fn set<T: PartialEq>(
+            criterion: &mut Option<T>,
+            value: T,
+            term: &str,
+        ) -> Result<(), String> {
+            match criterion {
+                Some(existing) if *existing != value => {
+                    Err(format!("`{}` contradicts an earlier term", term))
+                }
+                _ => {
+                    *criterion = Some(value);
+                    Ok(())
+                }
+            }
+        }
+        let positive = |term: &str| match negated {
+            true => Err(format!("`{}` can't be negated", term)),
+            false => Ok(()),
+        };
+
+        let (key, value) = match term.split_once(':') {
+            Some((key, value)) if !value.is_empty() => (key.to_ascii_lowercase(), Some(value)),
+            Some(_) => return Err(format!("expected a value after `{}`", term)),
+            None => (term.to_ascii_lowercase(), None),
+        };
+        match (key.as_str(), value) {
+            ("mainnet", None) => set(&mut self.testnet, negated, term),
+            ("testnet", None) => set(&mut self.testnet, !negated, term),
+            ("deprecated", None) => set(&mut self.deprecated, !negated, term),
+            ("flagged", None) => set(&mut self.flagged, !negated, term),
+            ("has", Some(field)) if field.eq_ignore_ascii_case("ens") => {
+                set(&mut self.has_ens, !negated, term)
+            }
+            ("has", Some(field)) if field.eq_ignore_ascii_case("explorer") => {
+                set(&mut self.has_explorer, !negated, term)
+            }
+            ("tag", Some(tag)) => {
+                match negated {
+                    true => self.without_tags.push(tag.into()),
+                    false => self.with_tags.push(tag.into()),
+                }
+                Ok(())
+            }
+            ("currency", Some(symbol)) => {
+                positive(term).and_then(|()| set(&mut self.currency, symbol.into(), term))
+            }
+            ("family", Some(family)) => {
+                positive(term).and_then(|()| set(&mut self.family, family.into(), term))
+            }
+            ("feature", Some(name)) => {
+                positive(term).and_then(|()| set(&mut self.feature, Feature::from_name(name), term))
+            }
+            _ => Err(format!("unknown term `{}`", term)),
+        }
+    }
+
+    /// The matching chains of the [`ChainRegistry::global`] registry, in
+    /// ascending chain id order.
+    pub fn execute(&self) -> Vec<&'static Chain> {
+        ChainRegistry::global()
+            .map(|registry| self.execute_on(registry))
+            .unwrap_or_default()
+    }
+
+    /// The matching chains of the registry, in ascending chain id order.
+    pub fn execute_on<'a>(&self, registry: &'a ChainRegistry) -> Vec<&'a Chain> {
+        // narrow down with the currency index first, when possible
+        match &self.currency {
+            Some(symbol) => registry.by_currency_symbol(symbol),
+            None => registry.iter().collect(),
+        }
+        .into_iter()
+        .filter(|chain| self.matches(chain))
+        .collect()
+    }
+}
+
+impl ChainRegistry {
+    /// The chains matching the [`ChainQuery::parse`]d query, in ascending
+    /// chain id order.
+    pub fn query_str(&self, query: &str) -> Result<Vec<&Chain>, Error> {
+        Ok(ChainQuery::parse(query)?.execute_on(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        let chains = ChainQuery::new()
+            .testnet(false)
+            .currency(&ethereum.native_currency.symbol)
+            .family(&ethereum.chain)
+            .execute();
+        assert!(chains.contains(&&ethereum));
+
+        assert_eq!(Chain::iter().len(), ChainQuery::new().execute().len());
+        assert!(ChainQuery::new()
+            .currency("NOT-A-SYMBOL")
+            .execute()
+            .is_empty());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            ChainQuery::new()
+                .testnet(false)
+                .currency("ETH")
+                .has_explorer(true)
+                .without_tag("l2"),
+            ChainQuery::parse("mainnet AND currency:ETH and has:explorer NOT tag:l2").unwrap()
+        );
+        assert_eq!(
+            ChainQuery::new().testnet(true).flagged(false),
+            ChainQuery::parse("NOT mainnet AND NOT flagged").unwrap()
+        );
+        assert_eq!(ChainQuery::new(), ChainQuery::parse("  ").unwrap());
+
+        for invalid in [
+            "mainnet AND",
+            "mainnet OR testnet",
+            "mainnet AND testnet",
+            "NOT currency:ETH",
+            "currency:",
+            "has:rpc",
+            "l2",
+            "NOT",
+        ] {
+            let error = ChainQuery::parse(invalid).expect_err(invalid);
+            assert_eq!(crate::error::Kind::Query, error.kind());
+        }
+    }
+
+    #[test]
+    fn query_str() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+
+        let chains = registry
+            .query_str("mainnet AND currency:ETH AND has:explorer")
+            .expect("Should parse the query");
+        assert!(chains.iter().any(|chain| chain.chain_id == 1));
+        assert!(chains.iter().all(|chain| !chain.is_testnet()));
+        assert!(registry.query_str("mainnet OR testnet").is_err());
+    }
+}
@@ -0,0 +1,532 @@
+//! Loading the chains without panicking on unreadable or malformed chain files.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    ops::Deref,
+    sync::Arc,
+    time::Duration,
+};
+
+use once_cell::sync::{Lazy, OnceCell};
+
+use crate::{
+    blocklist::RpcBlocklist,
+    embedded, error,
+    explorer::{self, LinkShortener},
+    ext,
+    gas::GasToken,
+    labels::LabelOverlay,
+    lint,
+    pin::Pin,
+    policy::{ContentPolicy, UrlPolicy},
+    sourcify, symbols,
+    tags::TagOverlay,
+    Chain, Error,
+};
+
+/// The registry behind [`Chain::get`] and [`Chain::iter`], set with
+/// [`ChainRegistry::set_global`] or otherwise loaded on first use with
+/// [`ChainRegistry::try_load`].
+static GLOBAL: OnceCell<Result<ChainRegistry, Error>> = OnceCell::new();
+
+/// The overlays of chains which weren't loaded by a [`RegistryBuilder`],
+/// e.g. ones created with [`Chain::new`].
+static DEFAULT_OVERLAYS: Lazy<Arc<Overlays>> = Lazy::new(Arc::default);
+
+/// Returned by [`chains`] when the [`GLOBAL`] registry failed to load.
+static EMPTY: BTreeMap<u64, Chain> = BTreeMap::new();
+
+/// The chains embedded at build time, see `build.rs`.
+#[derive(Debug, Clone)]
+pub struct ChainRegistry {
+    /// All the chains, ordered by chain id
+    chains: BTreeMap<u64, Chain>,
+    indexes: Indexes,
+    lints: Vec<lint::Lint>,
+    load_duration: Duration,
+    overlays: SharedOverlays,
+    rules: Rules,
+}
+
+/// The enrichers and checks of the [`RegistryBuilder`], kept by the registry
+/// to apply them to the chains of a [`ChainRegistry::transaction`] as well.
+#[derive(Clone, Default)]
+pub(crate) struct Rules {
+    pub(crate) enrichers: Vec<Arc<dyn ext::Enricher>>,
+    pub(crate) pins: Vec<Pin>,
+    pub(crate) content_policy: ContentPolicy,
+}
+
+impl fmt::Debug for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rules")
+            .field("enrichers", &self.enrichers.len())
+            .field("pins", &self.pins)
+            .field("content_policy", &self.content_policy)
+            .finish()
+    }
+}
+
+/// The data layered over the chain files by the [`RegistryBuilder`]
+/// methods, by default the data bundled with the crate.
+#[derive(Debug)]
+pub(crate) struct Overlays {
+    pub(crate) rpc_blocklist: RpcBlocklist,
+    pub(crate) url_policy: UrlPolicy,
+    /// Sorted and deduplicated
+    pub(crate) sourcify_chains: Vec<u64>,
+    pub(crate) gas_tokens: HashMap<u64, GasToken>,
+    pub(crate) tags: TagOverlay,
+    pub(crate) labels: LabelOverlay,
+    /// Current native currency symbols to their former ones, oldest first
+    pub(crate) symbol_history: HashMap<String, Vec<String>>,
+    /// Former lowercase explorer hosts to the current ones
+    pub(crate) explorer_redirects: HashMap<String, String>,
+    pub(crate) link_shortener: Option<Arc<dyn LinkShortener>>,
+}
+
+impl Default for Overlays {
+    fn default() -> Self {
+        Self {
+            rpc_blocklist: RpcBlocklist::bundled(),
+            url_policy: UrlPolicy::default(),
+            sourcify_chains: sourcify::BUNDLED.to_vec(),
+            gas_tokens: HashMap::new(),
+            tags: TagOverlay::default(),
+            labels: LabelOverlay::default(),
+            symbol_history: symbols::bundled(),
+            explorer_redirects: explorer::bundled_redirects(),
+            link_shortener: None,
+        }
+    }
+}
+
+/// The [`Overlays`] of a registry, shared with all of its chains.
+///
+/// Like the [`ext::ChainExtensions`], they are not part of the chain data.
+#[derive(Clone)]
+pub(crate) struct SharedOverlays(Arc<Overlays>);
+
+impl Default for SharedOverlays {
+    fn default() -> Self {
+        Self(DEFAULT_OVERLAYS.clone())
+    }
+}
+
+impl Deref for SharedOverlays {
+    type Target = Overlays;
+
+    fn deref(&self) -> &Overlays {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SharedOverlays {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Overlays")
+    }
+}
+
+impl PartialEq for SharedOverlays {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Loads a [`ChainRegistry`] with the enrichers and overlays, see [`ChainRegistry::builder`].
+///
+/// The overlay methods are documented in the modules of their data, e.g.
+/// [`RegistryBuilder::rpc_blocklist`].
+pub struct RegistryBuilder {
+    pub(crate) overlays: Overlays,
+    pub(crate) rules: Rules,
+}
+
+impl RegistryBuilder {
+    /// Invokes the enricher for every chain, after the ones added before it,
+    /// including the chains added later by a [`ChainRegistry::transaction`].
+    pub fn enricher(mut self, enricher: impl ext::Enricher + 'static) -> Self {
+        self.rules.enrichers.push(Arc::new(enricher));
+        self
+    }
+
+    pub fn enrichers(mut self, enrichers: &[Arc<dyn ext::Enricher>]) -> Self {
+        self.rules.enrichers.extend_from_slice(enrichers);
+        self
+    }
+
+    /// Deserializes all the embedded chain files, failing on the first
+    /// malformed one or violated [`Pin`] instead of panicking.
+    ///
+    /// The chains violating an enforced [`ContentPolicy`] are left out.
+    ///
+    /// Loading reads nothing but the chain files compiled into the binary,
+    /// so there's no directory or remote source to put a deadline on, see
+    /// [`ChainRegistry::load_duration`] for its cost.
+    pub fn load(self) -> Result<ChainRegistry, Error> {
+        ChainRegistry::load(embedded::chain_files(), self)
+    }
+}
+
+/// The secondary indexes to chain ids, built once at load time.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Indexes {
+    /// Lowercase name to the lowest chain id with that name
+    pub(crate) names: HashMap<String, u64>,
+    /// Short name to chain id
+    pub(crate) short_names: HashMap<String, u64>,
+    /// Network id to the chain ids, in ascending order
+    pub(crate) network_ids: HashMap<u64, Vec<u64>>,
+    /// Native currency symbol to the chain ids, in ascending order
+    pub(crate) currency_symbols: HashMap<String, Vec<u64>>,
+}
+
+impl Indexes {
+    /// Expects the chains in ascending chain id order.
+    fn build<'a>(chains: impl Iterator<Item = &'a Chain>) -> Self {
+        let mut indexes = Self::default();
+
+        for chain in chains {
+            // keep the lowest chain id for duplicate names
+            indexes
+                .names
+                .entry(chain.name.to_lowercase())
+                .or_insert(chain.chain_id);
+            indexes
+                .short_names
+                .insert(chain.short_name.clone(), chain.chain_id);
+            indexes
+                .network_ids
+                .entry(chain.network_id)
+                .or_default()
+                .push(chain.chain_id);
+            indexes
+                .currency_symbols
+                .entry(chain.native_currency.symbol.clone())
+                .or_default()
+                .push(chain.chain_id);
+        }
+
+        indexes
+    }
+}
+
+impl ChainRegistry {
+    /// A builder for a registry with enrichers or overlays other than the
+    /// bundled ones.
+    ///
+    /// ```
+    /// use evm_chains::{blocklist::RpcBlocklist, ChainRegistry};
+    ///
+    /// let registry = ChainRegistry::builder()
+    ///     .rpc_blocklist(RpcBlocklist::default())
+    ///     .load()
+    ///     .expect("Should load the chains");
+    /// ```
+    pub fn builder() -> RegistryBuilder {
+        RegistryBuilder {
+            overlays: Overlays::default(),
+            rules: Rules::default(),
+        }
+    }
+
+    /// Deserializes all the embedded chain files, failing on the first
+    /// malformed one instead of panicking.
+    pub fn try_load() -> Result<Self, Error> {
+        Self::builder().load()
+    }
+
+    /// Like [`ChainRegistry::try_load`], invoking the enrichers for every chain.
+    pub fn try_load_with(enrichers: &[Arc<dyn ext::Enricher>]) -> Result<Self, Error> {
+        Self::builder().enrichers(enrichers).load()
+    }
+
+    /// The registry used by [`Chain::get`], or the error it failed to load with.
+    pub fn global() -> Result<&'static ChainRegistry, &'static Error> {
+        GLOBAL.get_or_init(Self::try_load).as_ref()
+    }
+
+    /// Makes the registry the one used by [`Chain::get`], e.g. one loaded
+    /// with overlays by a [`RegistryBuilder`].
+    ///
+    /// Can be set only once and before the first use of the global registry,
+    /// otherwise the given registry is returned back.
+    pub fn set_global(registry: ChainRegistry) -> Result<(), Box<ChainRegistry>> {
+        GLOBAL.set(Ok(registry)).map_err(|registry| match registry {
+            Ok(registry) => Box::new(registry),
+            Err(_) => unreachable!("Only loaded registries are set"),
+        })
+    }
+
+    fn load(
+        chain_files: impl Iterator<Item = (u64, &'static str)>,
+        builder: RegistryBuilder,
+    ) -> Result<Self, Error> {
+        let started = std::time::Instant::now();
+        let overlays = SharedOverlays(Arc::new(builder.overlays));
+        let rules = builder.rules;
+        let mut chains = BTreeMap::new();
+        let mut lints = Vec::new();
+
+        for (chain_id, json) in chain_files {
+            let value = serde_json::from_str::<serde_json::Value>(json)
+                .map_err(|err| error::deserialize(err).with_chain_id(chain_id))?;
+            lints.extend(lint::lint_value(chain_id, &value));
+
+            let mut chain = serde_json::from_value::<Chain>(value)
+                .map_err(|err| error::deserialize(err).with_chain_id(chain_id))?;
+            lints.extend(lint::lint_chain(&chain));
+            let violations = rules.content_policy.check(&chain);
+            let rejected = rules.content_policy.enforce && !violations.is_empty();
+            lints.extend(violations);
+            if rejected {
+                continue;
+            }
+
+            attach(&mut chain, &overlays, &rules);
+            // duplicate ids are rejected by `build.rs`
+            chains.insert(chain_id, chain);
+        }
+
+        let registry = Self {
+            indexes: Indexes::build(chains.values()),
+            chains,
+            lints,
+            load_duration: started.elapsed(),
+            overlays,
+            rules,
+        };
+        registry.check_pins(registry.pins())?;
+
+        Ok(registry)
+    }
+
+    /// The pins checked when the chains load, see [`RegistryBuilder::pins`].
+    pub fn pins(&self) -> &[Pin] {
+        &self.rules.pins
+    }
+
+    /// The policy applied when the chains load, see [`RegistryBuilder::content_policy`].
+    pub fn content_policy(&self) -> &ContentPolicy {
+        &self.rules.content_policy
+    }
+
+    /// Attaches the overlays and the enricher extensions of the registry to
+    /// a chain which it didn't load.
+    pub(crate) fn attach(&self, chain: &mut Chain) {
+        attach(chain, &self.overlays, &self.rules);
+    }
+
+    pub(crate) fn indexes(&self) -> &Indexes {
+        &self.indexes
+    }
+
+    pub(crate) fn overlays(&self) -> &Overlays {
+        &self.overlays
+    }
+
+    /// Replaces all the chains, rebuilding the indexes.
+    pub(crate) fn set_chains(&mut self, chains: BTreeMap<u64, Chain>, lints: Vec<lint::Lint>) {
+        self.indexes = Indexes::build(chains.values());
+        self.chains = chains;
+        self.lints.extend(lints);
+    }
+
+    /// Keeps only the chains matching the predicate, rebuilding the indexes.
+    pub(crate) fn retain(mut self, mut predicate: impl FnMut(&Chain) -> bool) -> Self {
+        self.chains.retain(|_, chain| predicate(chain));
+        self.indexes = Indexes::build(self.chains.values());
+        self
+    }
+
+    pub fn get(&self, chain_id: u64) -> Option<&Chain> {
+        self.chains.get(&chain_id)
+    }
+
+    /// All the chains in ascending chain id order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Chain> + ExactSizeIterator {
+        self.chains.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.chains.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    /// The lints found while loading the chains.
+    pub fn lints(&self) -> &[lint::Lint] {
+        &self.lints
+    }
+
+    /// The time it took to read and deserialize all chain files.
+    pub fn load_duration(&self) -> Duration {
+        self.load_duration
+    }
+
+    /// The chain with the name, ignoring case, e.g. `"ethereum mainnet"`.
+    ///
+    /// Names are not guaranteed to be unique, the chain with the lowest
+    /// chain id is returned in that case.
+    pub fn by_name(&self, name: &str) -> Option<&Chain> {
+        self.indexes
+            .names
+            .get(&name.to_lowercase())
+            .and_then(|&chain_id| self.get(chain_id))
+    }
+
+    /// The chain with the short name, e.g. the `eth` prefix of an
+    /// [EIP-3770](https://eips.ethereum.org/EIPS/eip-3770) address `eth:0x...`.
+    ///
+    /// See [`ChainRegistry::resolve`] for suggestions when there's no such chain.
+    pub fn by_short_name(&self, short_name: &str) -> Option<&Chain> {
+        self.indexes
+            .short_names
+            .get(short_name)
+            .and_then(|&chain_id| self.get(chain_id))
+    }
+
+    /// All the chains, in ascending chain id order, with the network id.
+    ///
+    /// Some chains share their network id, see [`crate::replay`].
+    pub fn by_network_id(&self, network_id: u64) -> Vec<&Chain> {
+        self.chains_of(self.indexes.network_ids.get(&network_id))
+    }
+
+    /// All the chains, in ascending chain id order, whose native currency
+    /// has the symbol, e.g. `"ETH"` for Ethereum and many of its L2s.
+    pub fn by_currency_symbol(&self, symbol: &str) -> Vec<&Chain> {
+        self.chains_of(self.indexes.currency_symbols.get(symbol))
+    }
+
+    fn chains_of(&self, chain_ids: Option<&Vec<u64>>) -> Vec<&Chain> {
+        chain_ids
+            .into_iter()
+            .flatten()
+            .filter_map(|&chain_id| self.get(chain_id))
+            .collect()
+    }
+}
+
+impl Chain {
+    /// See [`ChainRegistry::by_name`].
+    pub fn by_name(name: &str) -> Option<&'static Chain> {
+        ChainRegistry::global().ok()?.by_name(name)
+    }
+
+    /// See [`ChainRegistry::by_short_name`].
+    pub fn by_short_name(short_name: &str) -> Option<&'static Chain> {
+        ChainRegistry::global().ok()?.by_short_name(short_name)
+    }
+
+    /// See [`ChainRegistry::by_network_id`].
+    pub fn by_network_id(network_id: u64) -> Vec<&'static Chain> {
+        ChainRegistry::global()
+            .map(|registry| registry.by_network_id(network_id))
+            .unwrap_or_default()
+    }
+
+    /// See [`ChainRegistry::by_currency_symbol`].
+    pub fn by_currency_symbol(symbol: &str) -> Vec<&'static Chain> {
+        ChainRegistry::global()
+            .map(|registry| registry.by_currency_symbol(symbol))
+            .unwrap_or_default()
+    }
+}
+
+fn attach(chain: &mut Chain, overlays: &SharedOverlays, rules: &Rules) {
+    chain.overlays = overlays.clone();
+
+    let mut ext = ext::ChainExtensions::default();
+    for enricher in &rules.enrichers {
+        enricher.enrich(chain, &mut ext);
+    }
+    chain.ext = ext;
+}
+
+/// The chains of the [`GLOBAL`] registry, empty if it failed to load.
+pub(crate) fn chains() -> &'static BTreeMap<u64, Chain> {
+    ChainRegistry::global()
+        .map(|registry| &registry.chains)
+        .unwrap_or(&EMPTY)
+}
+
+/// The overlays of the [`GLOBAL`] registry, the bundled ones if it failed to load.
+pub(crate) fn overlays() -> &'static Overlays {
+    ChainRegistry::global()
+        .map(ChainRegistry::overlays)
+        .unwrap_or(&DEFAULT_OVERLAYS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Kind;
+
+    #[test]
+    fn try_load() {
+        let registry = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let global = ChainRegistry::global().expect("Should load the embedded chains");
+
+        assert_eq!(global.len(), registry.len());
+        assert!(registry.get(1).is_some());
+        assert_eq!(registry.len(), chains().len());
+    }
+
+    #[test]
+    fn by_name() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert_eq!(Some(&ethereum), Chain::by_name(&ethereum.name));
+        assert_eq!(
+            Some(&ethereum),
+            Chain::by_name(&ethereum.name.to_uppercase())
+        );
+        assert_eq!(None, Chain::by_name("Not a chain name"));
+    }
+
+    #[test]
+    fn by_short_name() {
+        for chain_id in [1, 56, 137] {
+            let chain = Chain::get(chain_id).expect("Chain should exist");
+
+            assert_eq!(Some(&chain), Chain::by_short_name(&chain.short_name));
+        }
+        assert_eq!(None, Chain::by_short_name("not-a-short-name"));
+    }
+
+    #[test]
+    fn by_currency_symbol() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let chains = Chain::by_currency_symbol(&ethereum.native_currency.symbol);
+
+        assert!(chains.contains(&&ethereum));
+        assert!(chains
+            .iter()
+            .all(|chain| chain.native_currency.symbol == ethereum.native_currency.symbol));
+        assert!(Chain::by_currency_symbol("NOT-A-SYMBOL").is_empty());
+    }
+
+    #[test]
+    fn by_network_id() {
+        for chain_id in [1, 56, 137] {
+            let chain = Chain::get(chain_id).expect("Chain should exist");
+
+            assert!(Chain::by_network_id(chain.network_id).contains(&&chain));
+        }
+    }
+
+    #[test]
+    fn malformed_chain_file() {
+        let error = ChainRegistry::load(
+            [(1, "{\"name\": \"Ethereum\"")].into_iter(),
+            ChainRegistry::builder(),
+        )
+        .expect_err("Chain file should be malformed");
+
+        assert_eq!(Kind::Json, error.kind());
+        assert_eq!(Some(1), error.chain_id());
+    }
+}
@@ -0,0 +1,113 @@
+//! Audit of chains which can't be told apart by their `network_id`.
+//!
+//! Infrastructure keyed on the network id rather than the chain id (e.g.
+//! `net_version` based checks) can confuse such chains and replay
+//! transactions across them.
+use std::fmt;
+
+use crate::ChainRegistry;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkIdReport {
+    pub chain_id: u64,
+    pub network_id: u64,
+    /// Never empty
+    pub issues: Vec<NetworkIdIssue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkIdIssue {
+    /// The network id is not the same as the chain id.
+    DiffersFromChainId,
+    /// Other chains (sorted by chain id) declare the same network id.
+    SharedWith(Vec<u64>),
+}
+
+impl fmt::Display for NetworkIdIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkIdIssue::DiffersFromChainId => f.write_str(
+                "network id differs from the chain id, the replay protection id of EIP-155 signatures",
+            ),
+            NetworkIdIssue::SharedWith(chain_ids) => write!(
+                f,
+                "network id is shared with chain(s) {:?}, lookups by network id are ambiguous",
+                chain_ids
+            ),
+        }
+    }
+}
+
+impl ChainRegistry {
+    /// Reports, sorted by chain id, all the chains whose `network_id` differs
+    /// from their `chain_id` or collides with the one of another chain.
+    pub fn shared_network_ids(&self) -> Vec<NetworkIdReport> {
+        self.iter()
+            .filter_map(|chain| {
+                let mut issues = Vec::new();
+
+                if chain.network_id != chain.chain_id {
+                    issues.push(NetworkIdIssue::DiffersFromChainId);
+                }
+
+                // already sorted, the network id index is in ascending chain id order
+                let shared_with = self
+                    .by_network_id(chain.network_id)
+                    .into_iter()
+                    .map(|other| other.chain_id)
+                    .filter(|&chain_id| chain_id != chain.chain_id)
+                    .collect::<Vec<_>>();
+                if !shared_with.is_empty() {
+                    issues.push(NetworkIdIssue::SharedWith(shared_with));
+                }
+
+                (!issues.is_empty()).then_some(NetworkIdReport {
+                    chain_id: chain.chain_id,
+                    network_id: chain.network_id,
+                    issues,
+                })
+            })
+            .collect()
+    }
+}
+
+/// See [`ChainRegistry::shared_network_ids`].
+pub fn shared_network_ids() -> Vec<NetworkIdReport> {
+    ChainRegistry::global()
+        .map(ChainRegistry::shared_network_ids)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ethereum_classic_shares_network_id() {
+        let reports = shared_network_ids();
+
+        // Ethereum Classic uses network id 1
+        let classic = reports
+            .iter()
+            .find(|report| report.chain_id == 61)
+            .expect("Chain(61) should be reported");
+        assert_eq!(1, classic.network_id);
+        assert!(classic.issues.contains(&NetworkIdIssue::DiffersFromChainId));
+
+        let ethereum = reports
+            .iter()
+            .find(|report| report.chain_id == 1)
+            .expect("Chain(1) should be reported");
+        assert!(matches!(
+            &ethereum.issues[..],
+            [NetworkIdIssue::SharedWith(shared)] if shared.contains(&61)
+        ));
+
+        assert!(reports
+            .windows(2)
+            .all(|pair| pair[0].chain_id < pair[1].chain_id));
+
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        assert_eq!(reports, registry.shared_network_ids());
+    }
+}
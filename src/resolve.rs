@@ -0,0 +1,166 @@
+//! Resolving user input to a chain, with suggestions for typos.
+//!
+//! ```
+//! use evm_chains::ChainRegistry;
+//!
+//! let registry = ChainRegistry::global().expect("Should load the chains");
+//! assert_eq!(1, registry.resolve("eth").unwrap().chain_id);
+//!
+//! let not_found = registry.resolve("ethereum mainet").unwrap_err();
+//! assert_eq!(Some(1), not_found.suggestions.first().map(|chain| chain.chain_id));
+//! ```
+use std::{error::Error as StdError, fmt};
+
+use crate::{Chain, ChainRegistry};
+
+/// At most this many [`NotFound::suggestions`].
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// No chain has the name, short name or chain id, see [`ChainRegistry::resolve`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotFound<'a> {
+    pub query: String,
+    /// The chains with the closest names or short names, closest first
+    pub suggestions: Vec<&'a Chain>,
+}
+
+impl fmt::Display for NotFound<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown chain `{}`", self.query)?;
+
+        for (index, chain) in self.suggestions.iter().enumerate() {
+            let separator = if index == 0 { ", did you mean" } else { " or" };
+            write!(f, "{} `{}`", separator, chain.short_name)?;
+        }
+        if !self.suggestions.is_empty() {
+            f.write_str("?")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for NotFound<'_> {}
+
+impl ChainRegistry {
+    /// The chain with the short name, the name ignoring case or the decimal
+    /// chain id, e.g. `eth`, `Ethereum Mainnet` or `1`.
+    ///
+    /// Fails with the [`ChainRegistry::suggest`]ions otherwise, so a CLI or
+    /// an API can answer "did you mean `arb1`?".
+    pub fn resolve(&self, query: &str) -> Result<&Chain, NotFound<'_>> {
+        let query = query.trim();
+
+        self.by_short_name(query)
+            .or_else(|| self.by_name(query))
+            .or_else(|| query.parse().ok().and_then(|chain_id| self.get(chain_id)))
+            .ok_or_else(|| NotFound {
+                query: query.into(),
+                suggestions: self.suggest(query),
+            })
+    }
+
+    /// The chains whose name or short name is within a few typos of the
+    /// query, ignoring case, closest first and at most [`MAX_SUGGESTIONS`].
+    pub fn suggest(&self, query: &str) -> Vec<&Chain> {
+        let query = query.trim().to_lowercase().chars().collect::<Vec<_>>();
+        // about one typo every 3 characters
+        let max_distance = (query.len() / 3).max(1);
+
+        let mut suggestions = self
+            .iter()
+            .filter_map(|chain| {
+                let distance = [&chain.short_name, &chain.name]
+                    .into_iter()
+                    .map(|candidate| distance(&query, &candidate.to_lowercase()))
+                    .min()?;
+
+                (distance <= max_distance).then_some((distance, chain))
+            })
+            .collect::<Vec<_>>();
+        // stable, so equally close chains stay in ascending chain id order
+        suggestions.sort_by_key(|&(distance, _)| distance);
+
+        suggestions
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, chain)| chain)
+            .collect()
+    }
+}
+
+impl Chain {
+    /// See [`ChainRegistry::resolve`], never finds a chain nor suggests one
+    /// if the [`ChainRegistry::global`] registry failed to load.
+    pub fn resolve(query: &str) -> Result<&'static Chain, NotFound<'static>> {
+        match ChainRegistry::global() {
+            Ok(registry) => registry.resolve(query),
+            Err(_) => Err(NotFound {
+                query: query.trim().into(),
+                suggestions: Vec::new(),
+            }),
+        }
+    }
+}
+
+/// The Levenshtein distance of the characters to the candidate.
+fn distance(query: &[char], candidate: &str) -> usize {
+    let mut previous = (0..=query.len()).collect::<Vec<_>>();
+    let mut current = vec![0; query.len() + 1];
+
+    for (i, candidate_char) in candidate.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &query_char) in query.iter().enumerate() {
+            let substitution = previous[j] + usize::from(query_char != candidate_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[query.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+
+        for query in ["eth", "ethereum mainnet", " 1 "] {
+            assert_eq!(
+                Some(1),
+                Chain::resolve(query).ok().map(|chain| chain.chain_id)
+            );
+        }
+
+        let not_found = registry
+            .resolve("etth")
+            .expect_err("No chain is named `etth`");
+        assert_eq!("etth", not_found.query);
+        assert_eq!(
+            Some(1),
+            not_found.suggestions.first().map(|chain| chain.chain_id)
+        );
+        assert!(not_found
+            .to_string()
+            .starts_with("unknown chain `etth`, did you mean `eth`"));
+
+        let not_found = registry
+            .resolve("not a chain at all")
+            .expect_err("No chain is named so");
+        assert!(not_found.suggestions.is_empty());
+        assert_eq!("unknown chain `not a chain at all`", not_found.to_string());
+    }
+
+    #[test]
+    fn distances() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+
+        assert_eq!(0, distance(&chars("arb1"), "arb1"));
+        assert_eq!(1, distance(&chars("arb"), "arb1"));
+        assert_eq!(2, distance(&chars("arbitrum-on"), "arbitrum one"));
+        assert_eq!(3, distance(&chars(""), "eth"));
+    }
+}
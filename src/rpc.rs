@@ -0,0 +1,205 @@
+//! Typed RPC endpoints, classified by their url scheme.
+use std::{collections::HashMap, env};
+
+use crate::Chain;
+
+/// The url scheme of an RPC endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RpcScheme {
+    Https,
+    Http,
+    Wss,
+    Ws,
+}
+
+impl RpcScheme {
+    /// Whether the endpoint is served over HTTP (`https` or `http`).
+    pub fn is_http(self) -> bool {
+        matches!(self, RpcScheme::Https | RpcScheme::Http)
+    }
+
+    /// Whether the endpoint is a WebSocket (`wss` or `ws`).
+    pub fn is_websocket(self) -> bool {
+        matches!(self, RpcScheme::Wss | RpcScheme::Ws)
+    }
+
+    /// Whether the connection is encrypted (`https` or `wss`).
+    pub fn is_secure(self) -> bool {
+        matches!(self, RpcScheme::Https | RpcScheme::Wss)
+    }
+}
+
+/// An entry of [`Chain::rpc`] with its scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RpcUrl<'a> {
+    pub url: &'a str,
+    pub scheme: RpcScheme,
+    /// The url has a template variable, e.g. `${INFURA_API_KEY}`, which has
+    /// to be substituted before connecting
+    pub templated: bool,
+}
+
+impl<'a> RpcUrl<'a> {
+    /// Classifies the url by its scheme, ignoring case, `None` for any other scheme.
+    pub fn parse(url: &'a str) -> Option<Self> {
+        let (scheme, _) = url.split_once("://")?;
+        let scheme = match scheme.to_ascii_lowercase().as_str() {
+            "https" => RpcScheme::Https,
+            "http" => RpcScheme::Http,
+            "wss" => RpcScheme::Wss,
+            "ws" => RpcScheme::Ws,
+            _ => return None,
+        };
+
+        Some(Self {
+            url,
+            scheme,
+            templated: url
+                .split_once("${")
+                .is_some_and(|(_, variable)| variable.contains('}')),
+        })
+    }
+}
+
+/// Replaces every `${NAME}` variable of `url` with `lookup(NAME)`, `None` if
+/// any of them is unresolved.
+fn substitute(url: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let mut substituted = String::with_capacity(url.len());
+    let mut rest = url;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        substituted.push_str(&rest[..start]);
+        substituted.push_str(&lookup(&rest[start + 2..start + end])?);
+        rest = &rest[start + end + 1..];
+    }
+    substituted.push_str(rest);
+
+    Some(substituted)
+}
+
+impl Chain {
+    /// The [`Chain::rpc_urls`] with their `${NAME}` template variables
+    /// substituted by `vars`, skipping the ones with unresolved variables.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use evm_chains::Chain;
+    /// let ethereum = Chain::get(1).expect("Chain(1) should exist");
+    /// let vars = HashMap::from([("INFURA_API_KEY", "my-key")]);
+    ///
+    /// assert!(ethereum
+    ///     .rpc_urls_with(&vars)
+    ///     .all(|rpc_url| !rpc_url.contains("${")));
+    /// ```
+    pub fn rpc_urls_with<'a>(
+        &'a self,
+        vars: &'a HashMap<&str, &str>,
+    ) -> impl Iterator<Item = String> + 'a {
+        self.rpc_urls().filter_map(move |rpc_url| {
+            substitute(rpc_url, |name| {
+                vars.get(name).map(|value| value.to_string())
+            })
+        })
+    }
+
+    /// Like [`Chain::rpc_urls_with`], with the variables read from the
+    /// environment variables of the same name.
+    pub fn rpc_urls_from_env(&self) -> impl Iterator<Item = String> + '_ {
+        self.rpc_urls()
+            .filter_map(|rpc_url| substitute(rpc_url, |name| env::var(name).ok()))
+    }
+
+    /// The [`Chain::rpc_urls`] with a known scheme.
+    pub fn rpcs(&self) -> impl Iterator<Item = RpcUrl<'_>> {
+        self.rpc_urls().filter_map(RpcUrl::parse)
+    }
+
+    /// The [`Chain::rpcs`] with the given scheme.
+    pub fn rpcs_with_scheme(&self, scheme: RpcScheme) -> impl Iterator<Item = RpcUrl<'_>> {
+        self.rpcs().filter(move |rpc| rpc.scheme == scheme)
+    }
+
+    /// The `https` and `http` [`Chain::rpcs`].
+    pub fn http_rpcs(&self) -> impl Iterator<Item = RpcUrl<'_>> {
+        self.rpcs().filter(|rpc| rpc.scheme.is_http())
+    }
+
+    /// The [`Chain::rpcs`] usable without provider API keys, i.e. without
+    /// template variables.
+    pub fn public_rpcs(&self) -> impl Iterator<Item = RpcUrl<'_>> {
+        self.rpcs().filter(|rpc| !rpc.templated)
+    }
+
+    /// The `wss` and `ws` [`Chain::rpcs`].
+    pub fn websocket_rpcs(&self) -> impl Iterator<Item = RpcUrl<'_>> {
+        self.rpcs().filter(|rpc| rpc.scheme.is_websocket())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substituted_variables() {
+        let vars = HashMap::from([("INFURA_API_KEY", "key"), ("NETWORK", "mainnet")]);
+        let lookup = |name: &str| vars.get(name).map(|value| value.to_string());
+
+        assert_eq!(
+            Some("https://mainnet.infura.io/v3/key".to_string()),
+            substitute("https://${NETWORK}.infura.io/v3/${INFURA_API_KEY}", lookup)
+        );
+        assert_eq!(
+            None,
+            substitute(
+                "https://eth-mainnet.alchemyapi.io/v2/${ALCHEMY_API_KEY}",
+                lookup
+            )
+        );
+        assert_eq!(
+            Some("https://cloudflare-eth.com".to_string()),
+            substitute("https://cloudflare-eth.com", lookup)
+        );
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let infura = ethereum
+            .rpc_urls_with(&vars)
+            .find(|rpc_url| rpc_url.contains("infura.io"))
+            .expect("Chain(1) should have an Infura RPC");
+        assert!(infura.ends_with("/key"));
+        assert!(ethereum.rpc_urls_with(&HashMap::new()).count() < ethereum.rpc_urls().count());
+    }
+
+    #[test]
+    fn rpc_schemes() {
+        assert_eq!(
+            Some(RpcUrl {
+                url: "wss://mainnet.infura.io/ws/v3/${INFURA_API_KEY}",
+                scheme: RpcScheme::Wss,
+                templated: true,
+            }),
+            RpcUrl::parse("wss://mainnet.infura.io/ws/v3/${INFURA_API_KEY}")
+        );
+        assert_eq!(
+            Some(RpcScheme::Https),
+            RpcUrl::parse("HTTPS://cloudflare-eth.com").map(|rpc| rpc.scheme)
+        );
+        assert_eq!(None, RpcUrl::parse("ipc:///tmp/geth.ipc"));
+        assert_eq!(None, RpcUrl::parse("cloudflare-eth.com"));
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert!(ethereum.http_rpcs().count() > 0);
+        assert!(ethereum
+            .websocket_rpcs()
+            .all(|rpc| rpc.url.starts_with("ws")));
+        assert_eq!(
+            ethereum.rpcs().count(),
+            ethereum.http_rpcs().count() + ethereum.websocket_rpcs().count()
+        );
+        assert!(ethereum.public_rpcs().count() > 0);
+        assert!(ethereum.public_rpcs().all(|rpc| !rpc.url.contains("${")));
+        assert!(ethereum.public_rpcs().count() < ethereum.rpcs().count());
+    }
+}
@@ -0,0 +1,234 @@
+//! Time-boxed experimental chains on top of a [`ChainRegistry`].
+//!
+//! A [`Sandbox`] holds custom chains and overrides of existing ones, each
+//! with an expiry. Reads prefer the unexpired sandbox entries and fall back
+//! to the parent registry, which is never modified.
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::{Chain, ChainRegistry};
+
+#[derive(Debug, Clone)]
+pub struct Sandbox<'a> {
+    parent: &'a ChainRegistry,
+    entries: BTreeMap<u64, Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    chain: Chain,
+    expires_at: Instant,
+}
+
+impl Entry {
+    fn is_live(&self, now: Instant) -> bool {
+        now < self.expires_at
+    }
+}
+
+impl ChainRegistry {
+    /// An empty sandbox reading through to this registry.
+    pub fn sandbox(&self) -> Sandbox<'_> {
+        Sandbox {
+            parent: self,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'a> Sandbox<'a> {
+    /// Adds a custom chain, or overrides the parent one with the same chain
+    /// id, for the given time.
+    ///
+    /// Like the chains of a [`ChainRegistry::transaction`], the chain gets the
+    /// overlays and the enricher extensions of the parent.
+    pub fn insert(&mut self, chain: Chain, ttl: Duration) {
+        self.insert_until(chain, Instant::now() + ttl)
+    }
+
+    /// Like [`Sandbox::insert`], expiring at the given instant.
+    pub fn insert_until(&mut self, mut chain: Chain, expires_at: Instant) {
+        self.parent.attach(&mut chain);
+        self.entries
+            .insert(chain.chain_id, Entry { chain, expires_at });
+    }
+
+    /// Removes the sandbox entry, returning whether there was one.
+    pub fn remove(&mut self, chain_id: u64) -> bool {
+        self.entries.remove(&chain_id).is_some()
+    }
+
+    /// Drops the expired entries, they are already ignored by the reads.
+    pub fn purge_expired(&mut self) {
+        let now = Instant::now();
+
+        self.entries.retain(|_, entry| entry.is_live(now));
+    }
+
+    /// The unexpired sandbox chain, otherwise the parent one.
+    pub fn get(&self, chain_id: u64) -> Option<&Chain> {
+        match self.entries.get(&chain_id) {
+            Some(entry) if entry.is_live(Instant::now()) => Some(&entry.chain),
+            _ => self.parent.get(chain_id),
+        }
+    }
+
+    /// The merged chain with the name, ignoring case, see [`ChainRegistry::by_name`].
+    pub fn by_name(&self, name: &str) -> Option<&Chain> {
+        let name = name.to_lowercase();
+
+        self.find(self.parent.by_name(&name), |chain| {
+            chain.name.to_lowercase() == name
+        })
+    }
+
+    /// The merged chain with the short name, see [`ChainRegistry::by_short_name`].
+    pub fn by_short_name(&self, short_name: &str) -> Option<&Chain> {
+        self.find(self.parent.by_short_name(short_name), |chain| {
+            chain.short_name == short_name
+        })
+    }
+
+    /// The matching chain with the lowest chain id, among the unexpired
+    /// entries and the parent chains they don't override, starting from the
+    /// indexed parent chain.
+    fn find(&self, indexed: Option<&'a Chain>, matches: impl Fn(&Chain) -> bool) -> Option<&Chain> {
+        let now = Instant::now();
+        let sandboxed = self
+            .entries
+            .values()
+            .filter(|entry| entry.is_live(now))
+            .map(|entry| &entry.chain)
+            .find(|chain| matches(chain));
+        let parent = match indexed {
+            Some(chain) if self.is_sandboxed(chain.chain_id) => self
+                .parent
+                .iter()
+                .find(|chain| matches(chain) && !self.is_sandboxed(chain.chain_id)),
+            indexed => indexed,
+        };
+
+        match (sandboxed, parent) {
+            (Some(sandboxed), Some(parent)) if parent.chain_id < sandboxed.chain_id => Some(parent),
+            (sandboxed, parent) => sandboxed.or(parent),
+        }
+    }
+
+    /// Whether the chain comes from an unexpired sandbox entry.
+    pub fn is_sandboxed(&self, chain_id: u64) -> bool {
+        self.entries
+            .get(&chain_id)
+            .is_some_and(|entry| entry.is_live(Instant::now()))
+    }
+
+    /// The merged chains of the sandbox and the parent, in ascending chain id order.
+    pub fn iter(&self) -> impl Iterator<Item = &Chain> {
+        let now = Instant::now();
+        let mut chains = self
+            .parent
+            .iter()
+            .map(|chain| (chain.chain_id, chain))
+            .collect::<BTreeMap<_, _>>();
+        chains.extend(
+            self.entries
+                .values()
+                .filter(|entry| entry.is_live(now))
+                .map(|entry| (entry.chain.chain_id, &entry.chain)),
+        );
+
+        chains.into_values()
+    }
+
+    pub fn parent(&self) -> &'a ChainRegistry {
+        self.parent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_and_custom_chains() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        let mut sandbox = registry.sandbox();
+
+        let mut ethereum = registry.get(1).expect("Chain(1) should exist").clone();
+        ethereum.rpc = vec!["https://rpc.staging.example".into()];
+        sandbox.insert(ethereum.clone(), Duration::from_secs(60));
+
+        let mut custom = ethereum.clone();
+        custom.chain_id = u64::MAX;
+        sandbox.insert(custom, Duration::from_secs(60));
+
+        assert_eq!(Some(&ethereum), sandbox.get(1));
+        assert!(sandbox.is_sandboxed(u64::MAX));
+        assert_eq!(registry.get(56), sandbox.get(56));
+        assert_eq!(registry.len() + 1, sandbox.iter().count());
+        // the parent is untouched
+        assert_ne!(Some(&ethereum), registry.get(1));
+    }
+
+    #[test]
+    fn expired_entries() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        let mut sandbox = registry.sandbox();
+
+        let mut polygon = registry.get(137).expect("Chain(137) should exist").clone();
+        polygon.name = "Polygon Staging".into();
+        sandbox.insert_until(polygon, Instant::now());
+
+        assert_eq!(registry.get(137), sandbox.get(137));
+        assert!(!sandbox.is_sandboxed(137));
+
+        sandbox.purge_expired();
+        assert!(!sandbox.remove(137));
+    }
+
+    #[test]
+    fn merged_lookups() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        let mut sandbox = registry.sandbox();
+
+        let mut ethereum = registry.get(1).expect("Chain(1) should exist").clone();
+        ethereum.name = "Ethereum Staging".into();
+        ethereum.short_name = "eth-staging".into();
+        sandbox.insert(ethereum, Duration::from_secs(60));
+
+        let mut custom = registry.get(137).expect("Chain(137) should exist").clone();
+        custom.chain_id = u64::MAX;
+        custom.name = "Custom".into();
+        custom.short_name = "custom".into();
+        sandbox.insert(custom, Duration::from_secs(60));
+
+        let chain_id = |chain: Option<&Chain>| chain.map(|chain| chain.chain_id);
+        assert_eq!(Some(1), chain_id(sandbox.by_name("ethereum staging")));
+        assert_eq!(None, sandbox.by_name("Ethereum Mainnet"));
+        assert_eq!(Some(1), chain_id(sandbox.by_short_name("eth-staging")));
+        assert_eq!(None, sandbox.by_short_name("eth"));
+        assert_eq!(Some(u64::MAX), chain_id(sandbox.by_short_name("custom")));
+        assert_eq!(Some(137), chain_id(sandbox.by_short_name("matic")));
+        assert_eq!(Some(1), chain_id(registry.by_short_name("eth")));
+    }
+
+    #[test]
+    fn parent_overlays() {
+        let registry = ChainRegistry::builder()
+            .url_policy(crate::policy::UrlPolicy::ONION_ONLY)
+            .load()
+            .expect("Should load the embedded chains");
+        let mut sandbox = registry.sandbox();
+
+        sandbox.insert(
+            Chain::get(1).expect("Chain(1) should exist"),
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(
+            0,
+            sandbox.get(1).map_or(0, |chain| chain.rpc_urls().count())
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Sanitation of untrusted display strings from the chain files.
+use std::borrow::Cow;
+
+/// Cyrillic and Greek letters which are rendered (nearly) identically to a Latin one.
+///
+/// Lowercase Cyrillic letters shaped like small capitals (e.g. `в`, `н`, `т`)
+/// are not confusable with a Latin letter and are left out.
+const HOMOGLYPHS: &[(char, char)] = &[
+    // Cyrillic
+    ('а', 'a'),
+    ('е', 'e'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('с', 'c'),
+    ('у', 'y'),
+    ('х', 'x'),
+    ('ѕ', 's'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('А', 'A'),
+    ('В', 'B'),
+    ('Е', 'E'),
+    ('К', 'K'),
+    ('М', 'M'),
+    ('Н', 'H'),
+    ('О', 'O'),
+    ('Р', 'P'),
+    ('С', 'C'),
+    ('Т', 'T'),
+    ('У', 'Y'),
+    ('Х', 'X'),
+    ('Ѕ', 'S'),
+    ('І', 'I'),
+    ('Ј', 'J'),
+    // Greek
+    ('ο', 'o'),
+    ('ν', 'v'),
+    ('Α', 'A'),
+    ('Β', 'B'),
+    ('Ε', 'E'),
+    ('Ζ', 'Z'),
+    ('Η', 'H'),
+    ('Ι', 'I'),
+    ('Κ', 'K'),
+    ('Μ', 'M'),
+    ('Ν', 'N'),
+    ('Ο', 'O'),
+    ('Ρ', 'P'),
+    ('Τ', 'T'),
+    ('Υ', 'Y'),
+    ('Χ', 'X'),
+];
+
+/// Bidirectional formatting characters, which can reorder the rendered text.
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Zero-width characters which make two different strings look identical.
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'
+    )
+}
+
+fn homoglyph(c: char) -> Option<char> {
+    HOMOGLYPHS
+        .iter()
+        .find_map(|&(glyph, latin)| (glyph == c).then_some(latin))
+}
+
+/// Strips control, bidi and zero-width characters and, for strings mixing
+/// Latin letters with look-alike Cyrillic/Greek ones, replaces the homoglyphs
+/// with their Latin counterpart.
+///
+/// Returns [`Cow::Borrowed`] when nothing had to be changed.
+pub fn sanitize(value: &str) -> Cow<'_, str> {
+    let is_stripped = |c: char| c.is_control() || is_bidi_control(c) || is_invisible(c);
+    // only a mix of scripts is suspicious, names written fully in Cyrillic or Greek are fine
+    let has_latin = value.chars().any(|c| c.is_ascii_alphabetic());
+    let is_replaced = |c: char| has_latin && homoglyph(c).is_some();
+
+    if !value.chars().any(|c| is_stripped(c) || is_replaced(c)) {
+        return Cow::Borrowed(value);
+    }
+
+    Cow::Owned(
+        value
+            .chars()
+            .filter(|&c| !is_stripped(c))
+            .map(|c| match has_latin {
+                true => homoglyph(c).unwrap_or(c),
+                false => c,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_strings_are_borrowed() {
+        assert!(matches!(sanitize("Ethereum Mainnet"), Cow::Borrowed(_)));
+        assert!(matches!(sanitize("Сеть"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn strips_and_replaces() {
+        assert_eq!(
+            "Ethereum Mainnet",
+            sanitize("Ethereum\u{202E} Main\u{200B}net\n")
+        );
+        // Cyrillic `е` and `М`
+        assert_eq!("Ethereum Mainnet", sanitize("Ethеreum Мainnet"));
+        // Cyrillic `н` looks like a small capital `ʜ`, not like `h`
+        assert!(matches!(sanitize("Mainнet"), Cow::Borrowed(_)));
+    }
+}
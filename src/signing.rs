@@ -0,0 +1,97 @@
+//! What a transaction signer needs to know about a chain.
+use serde::{Deserialize, Serialize};
+
+use crate::{Chain, Feature};
+
+/// Everything a transaction signer needs from the chain, decoupled from
+/// the full [`Chain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SigningContext {
+    pub chain_id: u64,
+    /// The chain declares [EIP-155](https://eips.ethereum.org/EIPS/eip-155)
+    /// replay protection support
+    pub eip155: bool,
+    /// The chain declares [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
+    /// fee market support
+    pub eip1559: bool,
+}
+
+/// An [EIP-712](https://eips.ethereum.org/EIPS/eip-712) domain.
+///
+/// Serializes to the canonical JSON shape, e.g.
+/// `{"name":"Mail","version":"1","chainId":1,"verifyingContract":"0x..."}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip712Domain {
+    pub name: String,
+    pub version: String,
+    pub chain_id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifying_contract: Option<String>,
+}
+
+impl Chain {
+    /// The EIP-712 domain of a dapp on this chain, with the chain id taken
+    /// from the registry.
+    pub fn eip712_domain(
+        &self,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        verifying_contract: Option<&str>,
+    ) -> Eip712Domain {
+        Eip712Domain {
+            name: name.into(),
+            version: version.into(),
+            chain_id: self.chain_id,
+            verifying_contract: verifying_contract.map(Into::into),
+        }
+    }
+
+    pub fn signing_context(&self) -> SigningContext {
+        SigningContext {
+            chain_id: self.chain_id,
+            eip155: self.has_feature(&Feature::Eip155),
+            eip1559: self.supports_eip1559(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ethereum_signing_context() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert_eq!(
+            SigningContext {
+                chain_id: 1,
+                eip155: true,
+                eip1559: true,
+            },
+            ethereum.signing_context()
+        );
+    }
+
+    #[test]
+    fn eip712_domain_json() {
+        let polygon = Chain::get(137).expect("Chain(137) should exist");
+
+        let domain = polygon.eip712_domain(
+            "Ether Mail",
+            "1",
+            Some("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"),
+        );
+        assert_eq!(
+            r#"{"name":"Ether Mail","version":"1","chainId":137,"verifyingContract":"0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"}"#,
+            serde_json::to_string(&domain).unwrap()
+        );
+
+        let without_contract = polygon.eip712_domain("Ether Mail", "1", None);
+        assert_eq!(
+            r#"{"name":"Ether Mail","version":"1","chainId":137}"#,
+            serde_json::to_string(&without_contract).unwrap()
+        );
+    }
+}
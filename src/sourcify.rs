@@ -0,0 +1,74 @@
+//! [Sourcify](https://sourcify.dev) support of the chains.
+use crate::{registry, Chain, ChainRegistry, RegistryBuilder};
+
+/// The public Sourcify server
+pub const SOURCIFY_SERVER: &str = "https://sourcify.dev/server";
+
+/// Chain ids supported by the public Sourcify server, sorted.
+pub(crate) const BUNDLED: &[u64] = &[
+    1, 5, 10, 25, 30, 31, 56, 97, 100, 137, 250, 324, 1101, 1284, 1285, 8217, 8453, 17000, 42161,
+    42220, 43113, 43114, 44787, 59144, 80001, 80002, 84532, 421614, 534352, 11155111, 11155420,
+    1313161554,
+];
+
+impl RegistryBuilder {
+    /// Replaces the bundled list of chain ids supported by Sourcify, e.g. with
+    /// a freshly fetched one from the `/chains` endpoint of the server.
+    pub fn sourcify_chains(mut self, mut chain_ids: Vec<u64>) -> Self {
+        chain_ids.sort_unstable();
+        chain_ids.dedup();
+
+        self.overlays.sourcify_chains = chain_ids;
+        self
+    }
+}
+
+impl ChainRegistry {
+    /// The sorted chain ids supported by Sourcify, see [`RegistryBuilder::sourcify_chains`].
+    pub fn sourcify_chains(&self) -> &[u64] {
+        &self.overlays().sourcify_chains
+    }
+}
+
+/// See [`ChainRegistry::sourcify_chains`].
+pub fn sourcify_chains() -> &'static [u64] {
+    &registry::overlays().sourcify_chains
+}
+
+impl Chain {
+    /// Whether contracts on this chain can be verified on Sourcify, by the
+    /// Sourcify chains of the registry the chain was loaded by.
+    pub fn sourcify_supported(&self) -> bool {
+        self.overlays
+            .sourcify_chains
+            .binary_search(&self.chain_id)
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_is_sorted() {
+        assert!(BUNDLED.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn sourcify_support() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert!(ethereum.sourcify_supported());
+
+        let mut unknown = ethereum;
+        unknown.chain_id = u64::MAX;
+        assert!(!unknown.sourcify_supported());
+
+        let registry = ChainRegistry::builder()
+            .sourcify_chains(vec![137, 56, 137])
+            .load()
+            .expect("Should load the embedded chains");
+        assert_eq!(&[56, 137], registry.sourcify_chains());
+        assert!(!registry.get(1).unwrap().sourcify_supported());
+    }
+}
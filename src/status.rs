@@ -0,0 +1,74 @@
+//! The upstream lifecycle `status` of the chains.
+use serde::{Deserialize, Serialize};
+
+use crate::{Chain, ChainRegistry};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ChainStatus {
+    /// The default when the chain file has no `status`
+    #[default]
+    Active,
+    /// Shut down or superseded, e.g. Goerli by Sepolia
+    Deprecated,
+    /// Not launched yet
+    Incubating,
+    /// A status without a variant
+    #[serde(other)]
+    Unknown,
+}
+
+impl Chain {
+    pub fn is_deprecated(&self) -> bool {
+        self.status == ChainStatus::Deprecated
+    }
+
+    /// All the chains with the [`ChainStatus::Active`] status, in ascending chain id order.
+    pub fn active() -> impl DoubleEndedIterator<Item = &'static Chain> {
+        Chain::iter().filter(|chain| chain.status == ChainStatus::Active)
+    }
+}
+
+impl ChainRegistry {
+    /// Drops the deprecated chains, so none of the lookups of the registry
+    /// return them.
+    pub fn exclude_deprecated(self) -> Self {
+        self.retain(|chain| !chain.is_deprecated())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status() {
+        assert_eq!(
+            vec![
+                ChainStatus::Active,
+                ChainStatus::Deprecated,
+                ChainStatus::Incubating,
+                ChainStatus::Unknown
+            ],
+            serde_json::from_str::<Vec<ChainStatus>>(
+                r#"["active", "deprecated", "incubating", "retired"]"#
+            )
+            .unwrap()
+        );
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert_eq!(ChainStatus::Active, ethereum.status);
+        assert!(Chain::active().any(|chain| chain == &ethereum));
+    }
+
+    #[test]
+    fn exclude_deprecated() {
+        let registry = ChainRegistry::try_load()
+            .expect("Should load the embedded chains")
+            .exclude_deprecated();
+
+        assert!(registry.iter().all(|chain| !chain.is_deprecated()));
+        assert!(registry.get(1).is_some());
+    }
+}
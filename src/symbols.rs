@@ -0,0 +1,137 @@
+//! Native currency symbol changes, e.g. Polygon's `MATIC` becoming `POL`.
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{error, registry, Chain, ChainRegistry, Error, RegistryBuilder};
+
+/// The current symbols and their former ones, oldest first.
+const BUNDLED: &[(&str, &[&str])] = &[("POL", &["MATIC"])];
+
+/// The [`BUNDLED`] symbol history.
+pub(crate) fn bundled() -> HashMap<String, Vec<String>> {
+    BUNDLED
+        .iter()
+        .map(|&(symbol, former)| {
+            (
+                symbol.to_string(),
+                former.iter().map(|&former| former.into()).collect(),
+            )
+        })
+        .collect()
+}
+
+impl RegistryBuilder {
+    /// Replaces the bundled history of current symbols to their former ones,
+    /// oldest first, e.g. to track a rename before the crate knows about it.
+    pub fn symbol_history(mut self, overlay: HashMap<String, Vec<String>>) -> Self {
+        self.overlays.symbol_history = overlay;
+        self
+    }
+}
+
+/// Parses a symbol history overlay, a JSON object of current symbols to
+/// arrays of former ones, e.g. `{ "POL": ["MATIC"] }`.
+pub fn symbol_history_from_json(json: &str) -> Result<HashMap<String, Vec<String>>, Error> {
+    serde_json::from_str(json).map_err(error::deserialize)
+}
+
+/// See [`ChainRegistry::symbol_history`].
+pub fn symbol_history() -> &'static HashMap<String, Vec<String>> {
+    &registry::overlays().symbol_history
+}
+
+impl ChainRegistry {
+    /// The symbol history, see [`RegistryBuilder::symbol_history`].
+    pub fn symbol_history(&self) -> &HashMap<String, Vec<String>> {
+        &self.overlays().symbol_history
+    }
+
+    /// The symbols a currency had before its current `symbol`, oldest first.
+    pub fn former_symbols(&self, symbol: &str) -> &[String] {
+        former_symbols(self.symbol_history(), symbol)
+    }
+
+    /// Like [`ChainRegistry::by_currency_symbol`], also matching the
+    /// [`ChainRegistry::former_symbols`], so `"MATIC"` finds Polygon.
+    pub fn by_any_currency_symbol(&self, symbol: &str) -> Vec<&Chain> {
+        let renamed = self
+            .symbol_history()
+            .iter()
+            .filter(|(_, former)| former.iter().any(|former| former == symbol))
+            .map(|(current, _)| current.as_str());
+
+        std::iter::once(symbol)
+            .chain(renamed)
+            .flat_map(|symbol| self.by_currency_symbol(symbol))
+            .map(|chain| (chain.chain_id, chain))
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .collect()
+    }
+}
+
+fn former_symbols<'a>(history: &'a HashMap<String, Vec<String>>, symbol: &str) -> &'a [String] {
+    history.get(symbol).map(Vec::as_slice).unwrap_or_default()
+}
+
+impl Chain {
+    /// The symbols the native currency had before its current
+    /// [`NativeCurrency::symbol`](crate::NativeCurrency::symbol), oldest first,
+    /// in the symbol history of the registry the chain was loaded by.
+    pub fn former_currency_symbols(&self) -> &[String] {
+        former_symbols(&self.overlays.symbol_history, &self.native_currency.symbol)
+    }
+
+    /// See [`ChainRegistry::by_any_currency_symbol`].
+    pub fn by_any_currency_symbol(symbol: &str) -> Vec<&'static Chain> {
+        ChainRegistry::global()
+            .map(|registry| registry.by_any_currency_symbol(symbol))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn former_symbols() {
+        let polygon = Chain::get(137).expect("Chain(137) should exist");
+        let symbol = &polygon.native_currency.symbol;
+
+        assert!(Chain::by_any_currency_symbol(symbol).contains(&&polygon));
+        for former in polygon.former_currency_symbols() {
+            assert!(Chain::by_any_currency_symbol(former).contains(&&polygon));
+        }
+
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+        assert!(ethereum.former_currency_symbols().is_empty());
+    }
+
+    #[test]
+    fn user_supplied_history() {
+        let history = symbol_history_from_json(r#"{ "ETH": ["XETH"], "S": ["FTM"] }"#)
+            .expect("Should deserialize symbol history");
+
+        assert_eq!(vec!["FTM".to_string()], history["S"]);
+
+        let registry = ChainRegistry::builder()
+            .symbol_history(history)
+            .load()
+            .expect("Should load the embedded chains");
+        assert_eq!(&["FTM".to_string()], registry.former_symbols("S"));
+        assert_eq!(
+            &["XETH".to_string()],
+            registry.get(1).unwrap().former_currency_symbols()
+        );
+        // the overlay replaces the bundled history
+        assert!(registry
+            .get(137)
+            .unwrap()
+            .former_currency_symbols()
+            .is_empty());
+        assert_eq!(
+            &["MATIC".to_string()],
+            Chain::get(137).unwrap().former_currency_symbols()
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Static tables generated at build time, independent of the [`Chain`](crate::Chain) struct.
+//!
+//! All tables are sorted by chain id, which allows [`slice::binary_search_by_key`]:
+//!
+//! ```
+//! use evm_chains::tables::CHAIN_SYMBOLS;
+//!
+//! let symbol = CHAIN_SYMBOLS
+//!     .binary_search_by_key(&1, |&(chain_id, _)| chain_id)
+//!     .map(|index| CHAIN_SYMBOLS[index].1);
+//! ```
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/tables.rs"));
+}
+
+pub(crate) use generated::{DECIMALS, NAMES, SYMBOLS};
+
+/// Asserts at compile time the native currency symbols of the chains, so a
+/// change upstream (e.g. Polygon's `MATIC` becoming `POL`) fails the build.
+///
+/// ```
+/// evm_chains::assert_chains!(1 => "ETH", 137 => "MATIC" | "POL");
+/// ```
+///
+/// ```compile_fail
+/// evm_chains::assert_chains!(1 => "BNB");
+/// ```
+///
+/// A chain missing from the build fails it too, e.g. when excluded by the
+/// `EVM_CHAINS_FILTER` expression.
+#[macro_export]
+macro_rules! assert_chains {
+    ($($chain_id:literal => $($symbol:literal)|+),+ $(,)?) => {
+        $(
+            const _: () = ::core::assert!(
+                $crate::tables::symbol_is_one_of($chain_id, &[$($symbol),+]),
+                ::core::concat!(
+                    "Chain(",
+                    ::core::stringify!($chain_id),
+                    ") should exist with native currency symbol ",
+                    ::core::stringify!($($symbol)|+)
+                )
+            );
+        )+
+    };
+}
+
+/// A zero-sized marker of the chain `ID`, for APIs generic over a specific
+/// chain. The chain is looked up at compile time, when the marker is used.
+///
+/// ```
+/// use evm_chains::ChainIdOf;
+///
+/// type Ethereum = ChainIdOf<1>;
+///
+/// const SYMBOL: &str = Ethereum::SYMBOL;
+/// assert_eq!("ETH", SYMBOL);
+/// ```
+///
+/// A chain missing from the build fails it:
+///
+/// ```compile_fail
+/// const NAME: &str = evm_chains::ChainIdOf::<{ u64::MAX }>::NAME;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ChainIdOf<const ID: u64>;
+
+impl<const ID: u64> ChainIdOf<ID> {
+    const INDEX: usize = match index_of(ID) {
+        Some(index) => index,
+        None => panic!("The chain id is not in the build"),
+    };
+
+    pub const CHAIN_ID: u64 = ID;
+    pub const NAME: &'static str = NAMES[Self::INDEX].1;
+    /// The native currency symbol
+    pub const SYMBOL: &'static str = SYMBOLS[Self::INDEX].1;
+    /// The native currency decimals
+    pub const DECIMALS: u8 = DECIMALS[Self::INDEX].1;
+
+    /// The full chain from the global [`ChainRegistry`](crate::ChainRegistry).
+    ///
+    /// # Panics
+    ///
+    /// When the global registry failed to load, see [`ChainRegistry::global`](crate::ChainRegistry::global).
+    #[cfg(feature = "full")]
+    pub fn chain() -> &'static crate::Chain {
+        let _ = Self::INDEX;
+
+        crate::registry::chains()
+            .get(&ID)
+            .expect("The chains in the build should be loaded")
+    }
+}
+
+/// The index of the chain id in the generated tables, which all share the same order.
+pub(crate) const fn index_of(chain_id: u64) -> Option<usize> {
+    let (mut low, mut high) = (0, NAMES.len());
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        if NAMES[middle].0 == chain_id {
+            return Some(middle);
+        } else if NAMES[middle].0 < chain_id {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    None
+}
+
+/// Used by [`assert_chains!`], whether the chain exists and its native
+/// currency symbol is one of the `symbols`.
+#[doc(hidden)]
+pub const fn symbol_is_one_of(chain_id: u64, symbols: &[&str]) -> bool {
+    let symbol = match index_of(chain_id) {
+        Some(index) => SYMBOLS[index].1.as_bytes(),
+        None => return false,
+    };
+
+    let mut i = 0;
+    while i < symbols.len() {
+        if bytes_eq(symbol, symbols[i].as_bytes()) {
+            return true;
+        }
+        i += 1;
+    }
+
+    false
+}
+
+const fn bytes_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < left.len() {
+        if left[i] != right[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// `(chain id, name)`, e.g. `(1, "Ethereum Mainnet")`
+pub static CHAIN_NAMES: &[(u64, &str)] = &NAMES;
+
+/// `(chain id, native currency symbol)`, e.g. `(1, "ETH")`
+pub static CHAIN_SYMBOLS: &[(u64, &str)] = &SYMBOLS;
+
+/// `(chain id, native currency decimals)`, e.g. `(1, 18)`
+pub static CHAIN_DECIMALS: &[(u64, u8)] = &DECIMALS;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted_tables() {
+        assert!(CHAIN_NAMES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(CHAIN_NAMES.len(), CHAIN_SYMBOLS.len());
+        assert_eq!(CHAIN_NAMES.len(), CHAIN_DECIMALS.len());
+
+        assert!(CHAIN_SYMBOLS.contains(&(1, "ETH")));
+        assert!(CHAIN_DECIMALS.contains(&(1, 18)));
+    }
+
+    #[test]
+    fn anchor_chains() {
+        crate::assert_chains!(1 => "ETH", 56 => "BNB", 137 => "MATIC" | "POL");
+
+        assert!(symbol_is_one_of(1, &["ETH"]));
+        assert!(!symbol_is_one_of(1, &["BNB", "MATIC"]));
+        assert!(!symbol_is_one_of(u64::MAX, &["ETH"]));
+    }
+
+    #[test]
+    fn chain_id_markers() {
+        type Bsc = ChainIdOf<56>;
+
+        assert_eq!((56, "BNB", 18), (Bsc::CHAIN_ID, Bsc::SYMBOL, Bsc::DECIMALS));
+        #[cfg(feature = "full")]
+        assert_eq!(Bsc::NAME, Bsc::chain().name);
+    }
+}
@@ -0,0 +1,112 @@
+//! User maintained tags on chains, e.g. jurisdiction or compliance attributes.
+//!
+//! ```
+//! use evm_chains::{query::ChainQuery, tags::TagOverlay, ChainRegistry};
+//!
+//! let overlay = TagOverlay::from_json(r#"{ "56": ["restricted"] }"#).expect("Should parse overlay");
+//! let registry = ChainRegistry::builder()
+//!     .tag_overlay(overlay)
+//!     .load()
+//!     .expect("Should load the chains");
+//!
+//! let chains = ChainQuery::new().without_tag("restricted").execute_on(&registry);
+//! assert!(chains.iter().all(|chain| chain.chain_id != 56));
+//! ```
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error, registry, Chain, ChainRegistry, Error, RegistryBuilder};
+
+/// Tags by chain id, the chain data has no tags of its own.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TagOverlay {
+    pub tags: BTreeMap<u64, BTreeSet<String>>,
+}
+
+impl TagOverlay {
+    /// Parses a JSON object of chain ids to arrays of tags, e.g. `{ "1": ["restricted"] }`.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(error::deserialize)
+    }
+
+    pub fn tag(mut self, chain_id: u64, tag: impl Into<String>) -> Self {
+        self.tags.entry(chain_id).or_default().insert(tag.into());
+        self
+    }
+
+    /// The tags of the chain, sorted.
+    pub fn tags(&self, chain_id: u64) -> impl Iterator<Item = &str> {
+        self.tags
+            .get(&chain_id)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    pub fn has_tag(&self, chain_id: u64, tag: &str) -> bool {
+        self.tags
+            .get(&chain_id)
+            .is_some_and(|tags| tags.contains(tag))
+    }
+}
+
+impl RegistryBuilder {
+    /// Tags the registry's chains for [`Chain::tags`] and the tag filters of
+    /// [`ChainQuery`](crate::query::ChainQuery), no chain is tagged by default.
+    pub fn tag_overlay(mut self, overlay: TagOverlay) -> Self {
+        self.overlays.tags = overlay;
+        self
+    }
+}
+
+impl ChainRegistry {
+    /// The tags of the chains, see [`RegistryBuilder::tag_overlay`].
+    pub fn tag_overlay(&self) -> &TagOverlay {
+        &self.overlays().tags
+    }
+}
+
+/// See [`ChainRegistry::tag_overlay`].
+pub fn tag_overlay() -> &'static TagOverlay {
+    &registry::overlays().tags
+}
+
+impl Chain {
+    /// The tags of the chain in the tag overlay of the registry the chain was
+    /// loaded by, sorted.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.overlays.tags.tags(self.chain_id)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.overlays.tags.has_tag(self.chain_id, tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_overlay() {
+        let overlay = TagOverlay::from_json(r#"{ "1": ["sanctioned-infra", "restricted"] }"#)
+            .expect("Should deserialize overlay")
+            .tag(137, "restricted");
+
+        assert_eq!(
+            vec!["restricted", "sanctioned-infra"],
+            overlay.tags(1).collect::<Vec<_>>()
+        );
+        assert!(overlay.has_tag(137, "restricted"));
+        assert!(!overlay.has_tag(56, "restricted"));
+
+        let registry = ChainRegistry::builder()
+            .tag_overlay(overlay)
+            .load()
+            .expect("Should load the embedded chains");
+        assert!(registry.get(137).unwrap().has_tag("restricted"));
+        assert!(!Chain::get(137).unwrap().has_tag("restricted"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Rendering minimal `{{placeholder}}` templates against the chain data.
+use serde_json::Value;
+
+use crate::{error, Chain, Error};
+
+impl Chain {
+    /// Replaces every `{{path}}` placeholder with the field at the path.
+    ///
+    /// Paths are `.` separated keys of the serialized chain (e.g.
+    /// `nativeCurrency.symbol`) or array indices (e.g. `rpc.0`). A key
+    /// applied to an array reads the first element, so `{{explorers.url}}`
+    /// is the url of the first explorer.
+    ///
+    /// The `explorer` placeholder is the [`Chain::primary_explorer`] instead,
+    /// e.g. `{{explorer.url}}`, rendered empty if the chain has none.
+    ///
+    /// ```
+    /// # use evm_chains::Chain;
+    /// let ethereum = Chain::get(1).expect("Chain(1) should exist");
+    /// let message = ethereum
+    ///     .render_template("{{name}} ({{chainId}})")
+    ///     .expect("Should render the template");
+    ///
+    /// assert_eq!(format!("{} (1)", ethereum.name), message);
+    /// ```
+    pub fn render_template(&self, template: &str) -> Result<String, Error> {
+        let chain = serde_json::to_value(self).map_err(error::serialize)?;
+        let explorer = serde_json::to_value(self.primary_explorer()).map_err(error::serialize)?;
+
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+
+            let placeholder = &rest[start + 2..];
+            let end = placeholder
+                .find("}}")
+                .ok_or_else(|| error::template("unterminated `{{`"))?;
+            let path = placeholder[..end].trim();
+
+            let value = match path.strip_prefix("explorer") {
+                Some("") => Some(&explorer),
+                Some(field) if field.starts_with('.') && explorer.is_null() => Some(&Value::Null),
+                Some(field) if field.starts_with('.') => resolve(&explorer, &field[1..]),
+                _ => resolve(&chain, path),
+            };
+            match value {
+                Some(Value::String(string)) => rendered.push_str(string),
+                Some(Value::Null) => {}
+                Some(value) => rendered.push_str(&value.to_string()),
+                None => {
+                    return Err(error::template(format!("unknown placeholder `{}`", path))
+                        .with_chain_id(self.chain_id))
+                }
+            }
+
+            rest = &placeholder[end + 2..];
+        }
+        rendered.push_str(rest);
+
+        Ok(rendered)
+    }
+}
+
+fn resolve<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |value, segment| match value {
+            Value::Array(array) => match segment.parse::<usize>() {
+                Ok(index) => array.get(index),
+                Err(_) => array.first().and_then(|first| resolve(first, segment)),
+            },
+            Value::Object(object) => object.get(segment),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_template() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        let rendered = ethereum
+            .render_template("{{ name }}: {{rpc.0}} {{explorers.url}} {{nativeCurrency.decimals}}")
+            .expect("Should render the template");
+        assert_eq!(
+            format!(
+                "{}: {} {} 18",
+                ethereum.name, ethereum.rpc[0], ethereum.explorers[0].url
+            ),
+            rendered
+        );
+    }
+
+    #[test]
+    fn primary_explorer() {
+        let mut ethereum = Chain::get(1).expect("Chain(1) should exist");
+        let primary = ethereum
+            .primary_explorer()
+            .expect("Chain(1) should have an explorer")
+            .clone();
+
+        assert_eq!(
+            format!("{} {}", primary.name, primary.url),
+            ethereum
+                .render_template("{{explorer.name}} {{ explorer.url }}")
+                .expect("Should render the template")
+        );
+        assert!(ethereum.render_template("{{explorer.unknown}}").is_err());
+
+        ethereum.explorers.clear();
+        assert_eq!(
+            "explorer: ",
+            ethereum
+                .render_template("explorer: {{explorer.url}}")
+                .expect("Should render the template")
+        );
+    }
+
+    #[test]
+    fn invalid_placeholders() {
+        let ethereum = Chain::get(1).expect("Chain(1) should exist");
+
+        assert!(ethereum.render_template("{{unknown}}").is_err());
+        assert!(ethereum.render_template("{{explorerz}}").is_err());
+        assert!(ethereum.render_template("{{name").is_err());
+        assert_eq!(
+            "no placeholders",
+            ethereum.render_template("no placeholders").unwrap()
+        );
+    }
+}
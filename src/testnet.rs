@@ -0,0 +1,22 @@
+//! The definition of a testnet, shared by [`Chain::is_testnet`](crate::Chain::is_testnet)
+//! and the `testnet` condition of the `EVM_CHAINS_FILTER` in `build/filter.rs`.
+
+/// Name fragments, in lowercase, which only testnets use.
+const TESTNET_NAMES: [&str; 6] = [
+    "testnet", "devnet", "goerli", "sepolia", "holesky", "ropsten",
+];
+
+/// Whether the `network` is not `mainnet`, or the name contains e.g.
+/// `testnet`, `goerli` or `sepolia`.
+///
+/// Only when the `network` is empty, having faucets makes it a testnet.
+pub(crate) fn is_testnet(name: &str, network: &str, has_faucets: bool) -> bool {
+    let name = name.to_lowercase();
+
+    let testnet_network = match network {
+        "" => has_faucets,
+        network => !network.eq_ignore_ascii_case("mainnet"),
+    };
+
+    testnet_network || TESTNET_NAMES.iter().any(|testnet| name.contains(testnet))
+}
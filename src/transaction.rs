@@ -0,0 +1,259 @@
+//! All-or-nothing updates of an owned [`ChainRegistry`].
+//!
+//! ```
+//! use evm_chains::ChainRegistry;
+//!
+//! let mut registry = ChainRegistry::try_load().expect("Should load the chains");
+//! let mut ethereum = registry.get(1).expect("Chain(1) should exist").clone();
+//! ethereum.rpc = vec!["https://rpc.example.com".into()];
+//!
+//! registry
+//!     .transaction(|tx| {
+//!         tx.replace(ethereum);
+//!         tx.remove(137);
+//!         Ok(())
+//!     })
+//!     .expect("Transaction should commit");
+//!
+//! assert!(registry.get(137).is_none());
+//! ```
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{error, Chain, ChainRegistry, Error};
+
+/// The changes staged by [`ChainRegistry::transaction`], applied only if all
+/// of them are valid.
+#[derive(Debug, Default)]
+pub struct Transaction {
+    operations: Vec<Operation>,
+}
+
+#[derive(Debug)]
+enum Operation {
+    Insert(Chain),
+    Replace(Chain),
+    Remove(u64),
+}
+
+impl Transaction {
+    /// Adds a chain, the chain id should not be in the registry.
+    pub fn insert(&mut self, chain: Chain) {
+        self.operations.push(Operation::Insert(chain));
+    }
+
+    /// Overrides the chain with the same chain id, which should be in the registry.
+    pub fn replace(&mut self, chain: Chain) {
+        self.operations.push(Operation::Replace(chain));
+    }
+
+    /// Removes the chain, which should be in the registry.
+    pub fn remove(&mut self, chain_id: u64) {
+        self.operations.push(Operation::Remove(chain_id));
+    }
+}
+
+impl ChainRegistry {
+    /// Stages the changes of `update` and applies them all, or none if
+    /// `update` fails or the result is invalid, e.g. a duplicate short name.
+    ///
+    /// The inserted and replaced chains get the overlays and the enricher
+    /// extensions of the registry and are checked against its content policy,
+    /// the result against its pins, like the chains it loaded.
+    ///
+    /// The [`ChainRegistry::global`] registry can't be updated, only the
+    /// owned ones, e.g. from [`ChainRegistry::try_load`].
+    pub fn transaction(
+        &mut self,
+        update: impl FnOnce(&mut Transaction) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut transaction = Transaction::default();
+        update(&mut transaction)?;
+
+        let mut chains = self
+            .iter()
+            .map(|chain| (chain.chain_id, chain.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let mut lints = Vec::new();
+        for operation in transaction.operations {
+            match operation {
+                Operation::Insert(mut chain) => {
+                    lints.extend(self.content_policy().apply(&chain)?);
+                    self.attach(&mut chain);
+
+                    let chain_id = chain.chain_id;
+                    if chains.insert(chain_id, chain).is_some() {
+                        return Err(error::transaction(chain_id, "chain already exists"));
+                    }
+                }
+                Operation::Replace(mut chain) => {
+                    lints.extend(self.content_policy().apply(&chain)?);
+                    self.attach(&mut chain);
+
+                    let chain_id = chain.chain_id;
+                    if chains.insert(chain_id, chain).is_none() {
+                        return Err(error::transaction(chain_id, "chain doesn't exist"));
+                    }
+                }
+                Operation::Remove(chain_id) => {
+                    if chains.remove(&chain_id).is_none() {
+                        return Err(error::transaction(chain_id, "chain doesn't exist"));
+                    }
+                }
+            }
+        }
+
+        validate(&chains)?;
+        self.pins()
+            .iter()
+            .try_for_each(|pin| pin.check(chains.get(&pin.chain_id)))
+            .map_err(error::pin_violation)?;
+        self.set_chains(chains, lints);
+
+        Ok(())
+    }
+}
+
+/// The short names are unique, as guaranteed upstream for the EIP-3770 prefixes.
+fn validate(chains: &BTreeMap<u64, Chain>) -> Result<(), Error> {
+    let mut short_names = HashMap::with_capacity(chains.len());
+
+    for chain in chains.values() {
+        if let Some(other_id) = short_names.insert(chain.short_name.as_str(), chain.chain_id) {
+            return Err(error::transaction(
+                chain.chain_id,
+                format!(
+                    "short name `{}` is already used by chain {}",
+                    chain.short_name, other_id
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Kind,
+        ext::ChainExtensions,
+        pin,
+        policy::{ContentPolicy, UrlPolicy},
+    };
+
+    #[test]
+    fn commit() {
+        let mut registry = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let mut custom = registry.get(1).expect("Chain(1) should exist").clone();
+        custom.chain_id = u64::MAX;
+        custom.short_name = "custom".into();
+
+        registry
+            .transaction(|tx| {
+                tx.insert(custom.clone());
+                tx.remove(56);
+                Ok(())
+            })
+            .expect("Transaction should commit");
+
+        assert_eq!(Some(&custom), registry.get(u64::MAX));
+        assert_eq!(Some(&custom), registry.by_short_name("custom"));
+        assert!(registry.get(56).is_none());
+    }
+
+    #[test]
+    fn all_or_nothing() {
+        let mut registry = ChainRegistry::try_load().expect("Should load the embedded chains");
+        let len = registry.len();
+        let duplicate = registry.get(1).expect("Chain(1) should exist").clone();
+
+        let error = registry
+            .transaction(|tx| {
+                tx.remove(137);
+                tx.insert(duplicate);
+                Ok(())
+            })
+            .expect_err("Chain(1) already exists");
+
+        assert_eq!(Kind::Transaction, error.kind());
+        assert_eq!(Some(1), error.chain_id());
+        assert_eq!(len, registry.len());
+        assert!(registry.get(137).is_some());
+    }
+
+    #[test]
+    fn registry_rules() {
+        struct Enriched;
+
+        let mut registry = ChainRegistry::builder()
+            .url_policy(UrlPolicy::ONION_ONLY)
+            .enricher(|_: &Chain, ext: &mut ChainExtensions| ext.insert(Enriched))
+            .load()
+            .expect("Should load the embedded chains");
+        let mut custom = Chain::get(1).expect("Chain(1) should exist");
+        custom.chain_id = u64::MAX;
+        custom.short_name = "custom".into();
+
+        registry
+            .transaction(|tx| {
+                tx.insert(custom);
+                Ok(())
+            })
+            .expect("Transaction should commit");
+
+        let custom = registry.get(u64::MAX).expect("Chain should be inserted");
+        assert_eq!(0, custom.rpc_urls().count());
+        assert!(custom.ext::<Enriched>().is_some());
+    }
+
+    #[test]
+    fn pins_hold() {
+        let mut registry = ChainRegistry::builder()
+            .pins([pin!(137, native_symbol = "POL")])
+            .load()
+            .expect("Should load the embedded chains");
+
+        let error = registry
+            .transaction(|tx| {
+                tx.remove(137);
+                Ok(())
+            })
+            .expect_err("Chain(137) is pinned");
+        assert_eq!(Kind::Pin, error.kind());
+        assert!(registry.get(137).is_some());
+
+        let mut polygon = registry.get(137).expect("Chain(137) should exist").clone();
+        polygon.native_currency.symbol = "MATIC".into();
+        let error = registry
+            .transaction(|tx| {
+                tx.replace(polygon);
+                Ok(())
+            })
+            .expect_err("Chain(137) is pinned to POL");
+        assert_eq!(Kind::Pin, error.kind());
+    }
+
+    #[test]
+    fn enforced_content_policy() {
+        let mut registry = ChainRegistry::builder()
+            .content_policy(ContentPolicy {
+                max_name_length: Some(16),
+                enforce: true,
+                ..ContentPolicy::default()
+            })
+            .load()
+            .expect("Should load the embedded chains");
+        let mut ethereum = registry.get(1).expect("Chain(1) should exist").clone();
+        ethereum.name = "Ethereum Mainnet (renamed)".into();
+
+        let error = registry
+            .transaction(|tx| {
+                tx.replace(ethereum);
+                Ok(())
+            })
+            .expect_err("The name is longer than 16 characters");
+        assert_eq!(Kind::ContentPolicy, error.kind());
+        assert_eq!("Ethereum Mainnet", registry.get(1).unwrap().name);
+    }
+}
@@ -0,0 +1,168 @@
+//! Cross-checking the registry against the `chains.json` and `chains_mini.json`
+//! builds published on [chainid.network](https://chainid.network), to catch
+//! an embedded snapshot which silently drifted from upstream.
+//!
+//! The crate doesn't fetch the files, download them with any HTTP client and
+//! pass their contents to [`ChainRegistry::diff_upstream`].
+use std::{collections::BTreeMap, fmt};
+
+use serde_json::Value;
+
+use crate::{error, ChainRegistry, Error};
+
+/// The fields compared when the upstream entry has them, `chains_mini.json`
+/// entries have only some of them.
+const COMPARED_FIELDS: [&str; 8] = [
+    "name",
+    "shortName",
+    "chain",
+    "networkId",
+    "nativeCurrency",
+    "rpc",
+    "faucets",
+    "infoURL",
+];
+
+/// A difference between the registry and the upstream build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub chain_id: u64,
+    pub kind: DivergenceKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The chain is published upstream but not in the registry
+    Missing,
+    /// The chain is in the registry but not published upstream
+    Unpublished,
+    /// The field has a different value upstream
+    Field {
+        /// The JSON field, e.g. `nativeCurrency`
+        field: &'static str,
+        ours: Value,
+        upstream: Value,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            DivergenceKind::Missing => {
+                write!(
+                    f,
+                    "Chain({}): published upstream but missing",
+                    self.chain_id
+                )
+            }
+            DivergenceKind::Unpublished => {
+                write!(f, "Chain({}): not published upstream", self.chain_id)
+            }
+            DivergenceKind::Field {
+                field,
+                ours,
+                upstream,
+            } => write!(
+                f,
+                "Chain({}): `{}` is {} but {} upstream",
+                self.chain_id, field, ours, upstream
+            ),
+        }
+    }
+}
+
+impl ChainRegistry {
+    /// Compares the chains with the contents of an upstream `chains.json` or
+    /// `chains_mini.json`, a JSON array of chains, in ascending chain id order.
+    ///
+    /// Upstream entries without an integer `chainId` are ignored.
+    pub fn diff_upstream(&self, upstream_json: &str) -> Result<Vec<Divergence>, Error> {
+        let upstream = serde_json::from_str::<Vec<Value>>(upstream_json)
+            .map_err(error::deserialize)?
+            .into_iter()
+            .filter_map(|chain| Some((chain.get("chainId")?.as_u64()?, chain)))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut divergences = Vec::new();
+        for chain in self.iter() {
+            let Some(published) = upstream.get(&chain.chain_id) else {
+                divergences.push(Divergence {
+                    chain_id: chain.chain_id,
+                    kind: DivergenceKind::Unpublished,
+                });
+                continue;
+            };
+            let ours = serde_json::to_value(chain).expect("Chain should serialize to JSON value");
+
+            divergences.extend(COMPARED_FIELDS.into_iter().filter_map(|field| {
+                let upstream = published.get(field)?;
+                let ours = ours.get(field).unwrap_or(&Value::Null);
+
+                (ours != upstream).then(|| Divergence {
+                    chain_id: chain.chain_id,
+                    kind: DivergenceKind::Field {
+                        field,
+                        ours: ours.clone(),
+                        upstream: upstream.clone(),
+                    },
+                })
+            }));
+        }
+        divergences.extend(
+            upstream
+                .keys()
+                .filter(|&&chain_id| self.get(chain_id).is_none())
+                .map(|&chain_id| Divergence {
+                    chain_id,
+                    kind: DivergenceKind::Missing,
+                }),
+        );
+        divergences.sort_by_key(|divergence| divergence.chain_id);
+
+        Ok(divergences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upstream_divergences() {
+        let registry = ChainRegistry::global().expect("Should load the chains");
+        let mut upstream = registry
+            .iter()
+            .map(|chain| serde_json::to_value(chain).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Vec::<Divergence>::new(),
+            registry
+                .diff_upstream(&serde_json::to_string(&upstream).unwrap())
+                .expect("Should compare")
+        );
+
+        upstream[0]["name"] = "Renamed".into();
+        upstream.remove(1);
+        upstream.push(serde_json::json!({ "chainId": u64::MAX, "name": "New" }));
+        let second = registry.iter().nth(1).unwrap().chain_id;
+
+        let divergences = registry
+            .diff_upstream(&serde_json::to_string(&upstream).unwrap())
+            .expect("Should compare");
+        assert_eq!(3, divergences.len());
+        assert!(matches!(
+            &divergences[0].kind,
+            DivergenceKind::Field { field: "name", upstream, .. } if upstream == "Renamed"
+        ));
+        assert_eq!(
+            Divergence {
+                chain_id: second,
+                kind: DivergenceKind::Unpublished
+            },
+            divergences[1]
+        );
+        assert_eq!(DivergenceKind::Missing, divergences[2].kind);
+
+        assert!(registry.diff_upstream("{}").is_err());
+    }
+}
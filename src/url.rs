@@ -0,0 +1,108 @@
+//! Small helpers for the URL strings found in the chain files.
+
+/// Splits `scheme://credentials@host:port/path` into `scheme` and `host:port`.
+fn scheme_and_host_port(url: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_credentials, host_port)| host_port);
+
+    Some((scheme, host_port))
+}
+
+/// Returns the host part of `url`, without scheme, credentials, port and path.
+///
+/// E.g. `https://user@etherscan.io:443/tx` => `etherscan.io`
+pub(crate) fn host(url: &str) -> Option<&str> {
+    let (_scheme, host_port) = scheme_and_host_port(url)?;
+    let host = host_port
+        .split_once(':')
+        .map_or(host_port, |(host, _port)| host);
+
+    (!host.is_empty()).then_some(host)
+}
+
+/// Returns the explicit port of `url` or the default port of its scheme.
+pub(crate) fn port(url: &str) -> Option<u16> {
+    let (scheme, host_port) = scheme_and_host_port(url)?;
+
+    match host_port.split_once(':') {
+        Some((_host, port)) => port.parse().ok(),
+        None => match scheme.to_ascii_lowercase().as_str() {
+            "https" | "wss" => Some(443),
+            "http" | "ws" => Some(80),
+            _ => None,
+        },
+    }
+}
+
+/// Returns `url` with its [`host`] replaced, keeping everything else.
+///
+/// E.g. `https://old.example/tx/0x1` => `https://new.example/tx/0x1`
+pub(crate) fn replace_host(url: &str, new_host: &str) -> Option<String> {
+    let host = host(url)?;
+    // `host` is a sub-slice of `url`
+    let start = host.as_ptr() as usize - url.as_ptr() as usize;
+
+    Some(format!(
+        "{}{}{}",
+        &url[..start],
+        new_host,
+        &url[start + host.len()..]
+    ))
+}
+
+/// Whether `host` is `domain` itself or one of its subdomains.
+pub(crate) fn is_same_or_subdomain(host: &str, domain: &str) -> bool {
+    host.eq_ignore_ascii_case(domain)
+        || (host.len() > domain.len()
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+            && host.as_bytes()[host.len() - domain.len()..].eq_ignore_ascii_case(domain.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_host() {
+        assert_eq!(Some("etherscan.io"), host("https://etherscan.io"));
+        assert_eq!(
+            Some("etherscan.io"),
+            host("https://user@etherscan.io:443/tx?a=1")
+        );
+        assert_eq!(
+            Some("mainnet.infura.io"),
+            host("wss://mainnet.infura.io/ws/v3/${INFURA_API_KEY}")
+        );
+        assert_eq!(None, host("etherscan.io"));
+        assert_eq!(None, host("https://"));
+    }
+
+    #[test]
+    fn url_port() {
+        assert_eq!(Some(443), port("https://etherscan.io/tx"));
+        assert_eq!(Some(443), port("wss://mainnet.infura.io/ws"));
+        assert_eq!(Some(80), port("http://rpc.example.com"));
+        assert_eq!(Some(8545), port("http://localhost:8545"));
+        assert_eq!(None, port("ipfs://Qm"));
+    }
+
+    #[test]
+    fn url_replace_host() {
+        assert_eq!(
+            Some("https://user@new.example:8443/tx?a=1".to_string()),
+            replace_host("https://user@old.example:8443/tx?a=1", "new.example")
+        );
+        assert_eq!(None, replace_host("old.example", "new.example"));
+    }
+
+    #[test]
+    fn subdomains() {
+        assert!(is_same_or_subdomain("etherscan.io", "etherscan.io"));
+        assert!(is_same_or_subdomain("goerli.etherscan.io", "etherscan.io"));
+        assert!(!is_same_or_subdomain("notetherscan.io", "etherscan.io"));
+        assert!(!is_same_or_subdomain("aé12345", "12345"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Immutable filtered views of a [`ChainRegistry`].
+//!
+//! A [`ChainView`] borrows the chains of the registry instead of cloning
+//! them, so one loaded registry can serve several products, e.g. a
+//! production view without the testnets and the flagged chains:
+//!
+//! ```
+//! use evm_chains::{query::ChainQuery, ChainRegistry};
+//!
+//! let registry = ChainRegistry::global().expect("Should load the chains");
+//! let production = registry.view(ChainQuery::new().testnet(false).flagged(false));
+//!
+//! assert!(production.iter().all(|chain| !chain.is_testnet() && !chain.is_flagged()));
+//! ```
+use crate::{query::ChainQuery, Chain, ChainRegistry};
+
+/// The chains of a registry matching a [`ChainQuery`], see [`ChainRegistry::view`].
+#[derive(Debug, Clone)]
+pub struct ChainView<'a> {
+    registry: &'a ChainRegistry,
+    query: ChainQuery,
+    /// In ascending order
+    chain_ids: Vec<u64>,
+}
+
+impl ChainRegistry {
+    /// A view of the chains matching the query, evaluated once.
+    pub fn view(&self, query: ChainQuery) -> ChainView<'_> {
+        let chain_ids = query
+            .execute_on(self)
+            .into_iter()
+            .map(|chain| chain.chain_id)
+            .collect();
+
+        ChainView {
+            registry: self,
+            query,
+            chain_ids,
+        }
+    }
+}
+
+impl<'a> ChainView<'a> {
+    pub fn contains(&self, chain_id: u64) -> bool {
+        self.chain_ids.binary_search(&chain_id).is_ok()
+    }
+
+    pub fn get(&self, chain_id: u64) -> Option<&'a Chain> {
+        self.contains(chain_id)
+            .then(|| self.registry.get(chain_id))
+            .flatten()
+    }
+
+    /// The chains of the view, in ascending chain id order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &'a Chain> + ExactSizeIterator + '_ {
+        let registry = self.registry;
+
+        self.chain_ids.iter().map(move |&chain_id| {
+            registry
+                .get(chain_id)
+                .expect("Chains of the view should be in the registry")
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.chain_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chain_ids.is_empty()
+    }
+
+    /// See [`ChainRegistry::by_name`], among the chains of the view.
+    pub fn by_name(&self, name: &str) -> Option<&'a Chain> {
+        match self.registry.by_name(name) {
+            Some(chain) if self.contains(chain.chain_id) => Some(chain),
+            // a chain with the same name but a higher chain id may be in the view
+            Some(_) => {
+                let name = name.to_lowercase();
+                self.iter().find(|chain| chain.name.to_lowercase() == name)
+            }
+            None => None,
+        }
+    }
+
+    /// See [`ChainRegistry::by_short_name`], among the chains of the view.
+    pub fn by_short_name(&self, short_name: &str) -> Option<&'a Chain> {
+        self.registry
+            .by_short_name(short_name)
+            .filter(|chain| self.contains(chain.chain_id))
+    }
+
+    /// The query the view was created with.
+    pub fn query(&self) -> &ChainQuery {
+        &self.query
+    }
+
+    pub fn registry(&self) -> &'a ChainRegistry {
+        self.registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn production_view() {
+        let registry = ChainRegistry::global().expect("Should load the embedded chains");
+        let production = registry.view(ChainQuery::new().testnet(false).flagged(false));
+
+        assert!(production.contains(1));
+        assert!(!production.contains(2000), "Dogechain has red flags");
+        assert!(production.get(11155111).is_none());
+        assert!(registry.get(11155111).is_some());
+        assert!(production.by_short_name("eth").is_some());
+        assert_eq!(
+            None,
+            production.by_name(&registry.get(5).expect("Chain(5) should exist").name)
+        );
+        assert!(std::ptr::eq(
+            registry.get(1).unwrap(),
+            production.get(1).unwrap()
+        ));
+        assert_eq!(
+            registry
+                .iter()
+                .filter(|chain| production.query().matches(chain))
+                .count(),
+            production.len()
+        );
+    }
+}
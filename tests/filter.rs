@@ -0,0 +1,29 @@
+//! Runs the tests of the `EVM_CHAINS_FILTER` expressions used by `build.rs`.
+#[path = "../build/filter.rs"]
+mod filter;
+#[path = "../src/testnet.rs"]
+mod testnet;
+
+use filter::Filter;
+use serde_json::json;
+
+#[test]
+fn eip1559_ignores_case() {
+    let chain = json!({ "chainId": 1, "features": [{ "name": "eip1559" }] });
+
+    assert!(Filter::parse("eip1559").unwrap().matches(&chain));
+    assert!(!Filter::parse("eip1559")
+        .unwrap()
+        .matches(&json!({ "chainId": 1, "features": [{ "name": "EIP155" }] })));
+}
+
+#[test]
+fn string_chain_ids() {
+    let filter = Filter::parse("id=56").unwrap();
+
+    assert!(filter.matches(&json!({ "chainId": 56 })));
+    assert!(filter.matches(&json!({ "chainId": "56" })));
+    assert!(filter.matches(&json!({ "chainId": " 56 " })));
+    assert!(filter.matches(&json!({ "chainId": 56.0 })));
+    assert!(!filter.matches(&json!({ "chainId": "5" })));
+}